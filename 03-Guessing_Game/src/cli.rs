@@ -0,0 +1,171 @@
+use std::path::PathBuf;
+
+use crate::difficulty::Difficulty;
+use crate::solver::STRATEGIES;
+
+// Everything the player can set on the command line.
+
+#[derive(Debug)]
+pub struct Options {
+    pub difficulty: Option<Difficulty>,
+    pub seed: Option<u64>,
+    pub name: Option<String>,
+    pub show_scores: bool,
+    pub scores_file: Option<PathBuf>,
+    pub auto: bool,
+    pub strategy: String,
+    pub bench: Option<u32>,
+    pub reverse: bool,
+    pub players: Vec<String>,
+    pub rounds: u32,
+    pub serve: Option<String>,
+    pub connect: Option<String>,
+    pub float: bool,
+    pub epsilon: f64,
+}
+
+pub const USAGE: &str = "\
+Usage: guessing_game [OPTIONS]
+
+Options:
+  -d, --difficulty <LEVEL>   easy, normal, hard or custom:LOW..HIGH:GUESSES
+                             (custom ranges may be negative, e.g. custom:-500..500:12)
+      --seed <N>             pick the secret number with a seeded RNG
+      --name <NAME>          name to record in the high-score table
+      --scores               print the high-score table and exit
+      --scores-file <PATH>   keep high scores in PATH instead of the data dir
+      --auto                 let the computer play against itself
+      --strategy <NAME>      solver used by --auto: binary (default) or random
+      --bench <GAMES>        compare the solver strategies over GAMES games
+      --reverse              you pick the number and the computer guesses
+      --players <A,B,..>     hot-seat match between two or more named players
+      --rounds <N>           play the match as best-of-N rounds (default 1)
+      --serve <ADDR>         host games over TCP, e.g. --serve 127.0.0.1:4000
+      --connect <ADDR>       play against a --serve server
+      --float                guess real numbers instead of whole ones
+      --epsilon <E>          how close a --float guess has to be (default 0.5)
+  -h, --help                 print this help";
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            difficulty: None,
+            seed: None,
+            name: None,
+            show_scores: false,
+            scores_file: None,
+            auto: false,
+            strategy: String::from("binary"),
+            bench: None,
+            reverse: false,
+            players: Vec::new(),
+            rounds: 1,
+            serve: None,
+            connect: None,
+            float: false,
+            epsilon: 0.5,
+        }
+    }
+}
+
+impl Options {
+    // Parses the arguments that follow the program name.
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Options, String> {
+        let mut options = Options::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-d" | "--difficulty" => {
+                    let level = value(&mut args, &arg)?;
+                    options.difficulty = Some(level.parse()?);
+                }
+                "--seed" => {
+                    let seed = value(&mut args, &arg)?;
+                    let seed = seed
+                        .parse()
+                        .map_err(|_| format!("--seed needs a whole number, got '{}'", seed))?;
+                    options.seed = Some(seed);
+                }
+                "--name" => options.name = Some(value(&mut args, &arg)?),
+                "--scores" => options.show_scores = true,
+                "--scores-file" => options.scores_file = Some(value(&mut args, &arg)?.into()),
+                "--auto" => options.auto = true,
+                "--strategy" => {
+                    let strategy = value(&mut args, &arg)?;
+                    if !STRATEGIES.contains(&strategy.as_str()) {
+                        return Err(format!(
+                            "unknown strategy '{}' (expected {})",
+                            strategy,
+                            STRATEGIES.join(" or ")
+                        ));
+                    }
+                    options.strategy = strategy;
+                }
+                "--bench" => {
+                    let games = value(&mut args, &arg)?;
+                    match games.parse() {
+                        Ok(games) if games > 0 => options.bench = Some(games),
+                        _ => return Err(format!("--bench needs a positive number of games, got '{}'", games)),
+                    }
+                }
+                "--reverse" => options.reverse = true,
+                "--players" => {
+                    let players: Vec<String> = value(&mut args, &arg)?
+                        .split(',')
+                        .map(|name| name.trim().to_string())
+                        .filter(|name| !name.is_empty())
+                        .collect();
+                    if players.len() < 2 {
+                        return Err(String::from("--players needs at least two names, e.g. --players ann,bob"));
+                    }
+                    for (i, name) in players.iter().enumerate() {
+                        if players[..i].contains(name) {
+                            return Err(format!("'{}' is playing twice", name));
+                        }
+                    }
+                    options.players = players;
+                }
+                "--rounds" => {
+                    let rounds = value(&mut args, &arg)?;
+                    match rounds.parse() {
+                        Ok(rounds) if rounds > 0 => options.rounds = rounds,
+                        _ => return Err(format!("--rounds needs a positive number, got '{}'", rounds)),
+                    }
+                }
+                "--serve" => options.serve = Some(value(&mut args, &arg)?),
+                "--connect" => options.connect = Some(value(&mut args, &arg)?),
+                "--float" => options.float = true,
+                "--epsilon" => {
+                    let epsilon = value(&mut args, &arg)?;
+                    match epsilon.parse::<f64>() {
+                        Ok(epsilon) if epsilon.is_finite() && epsilon >= 0.0 => options.epsilon = epsilon,
+                        _ => return Err(format!("--epsilon needs a number of zero or more, got '{}'", epsilon)),
+                    }
+                    options.float = true;
+                }
+                "-h" | "--help" => return Err(String::from(USAGE)),
+                other => return Err(format!("unexpected argument '{}'\n\n{}", other, USAGE)),
+            }
+        }
+
+        // Everything except the plain single-player game still deals in whole
+        // numbers.
+        let whole_numbers_only = options.auto
+            || options.reverse
+            || options.bench.is_some()
+            || !options.players.is_empty()
+            || options.serve.is_some()
+            || options.connect.is_some();
+        if options.float && whole_numbers_only {
+            return Err(String::from("--float only works with the normal single-player game"));
+        }
+
+        Ok(options)
+    }
+}
+
+fn value<I: Iterator<Item = String>>(args: &mut I, flag: &str) -> Result<String, String> {
+    args.next()
+        .ok_or_else(|| format!("{} needs a value\n\n{}", flag, USAGE))
+}
@@ -0,0 +1,187 @@
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+use crate::game::Rules;
+
+// A difficulty level decides the range the secret number is picked from and
+// how many guesses the player gets before they lose.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    Custom { low: i64, high: i64, max_guesses: u32 },
+}
+
+impl Difficulty {
+    // Custom levels are checked here so the rest of the game can rely on
+    // `low <= high` and on there being at least one guess.
+    pub fn custom(low: i64, high: i64, max_guesses: u32) -> Result<Difficulty, String> {
+        if low > high {
+            return Err(format!("the range {}..{} is empty", low, high));
+        }
+        if high == i64::MAX {
+            return Err(format!("the highest allowed number is {}", i64::MAX - 1));
+        }
+        if max_guesses == 0 {
+            return Err(String::from("you need at least one guess"));
+        }
+
+        Ok(Difficulty::Custom { low, high, max_guesses })
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+            Difficulty::Custom { .. } => "custom",
+        }
+    }
+
+    // The lowest and highest possible secret numbers, both inclusive.
+    pub fn low(&self) -> i64 {
+        match self {
+            Difficulty::Easy | Difficulty::Normal | Difficulty::Hard => 1,
+            Difficulty::Custom { low, .. } => *low,
+        }
+    }
+
+    pub fn high(&self) -> i64 {
+        match self {
+            Difficulty::Easy => 50,
+            Difficulty::Normal => 100,
+            Difficulty::Hard => 1000,
+            Difficulty::Custom { high, .. } => *high,
+        }
+    }
+
+    pub fn max_guesses(&self) -> u32 {
+        match self {
+            Difficulty::Easy => 10,
+            Difficulty::Normal => 7,
+            Difficulty::Hard => 10,
+            Difficulty::Custom { max_guesses, .. } => *max_guesses,
+        }
+    }
+
+    pub fn rules(&self) -> Rules<i64> {
+        Rules {
+            low: self.low(),
+            high: self.high(),
+            max_guesses: self.max_guesses(),
+            tolerance: 0,
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} ({}..{}, {} guesses)",
+            self.name(),
+            self.low(),
+            self.high(),
+            self.max_guesses()
+        )
+    }
+}
+
+// Levels are written as `easy`, `normal`, `hard` or `custom:LOW..HIGH:GUESSES`,
+// e.g. `custom:1..500:9` or `custom:-500..500:12`.
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Names are case-insensitive, `custom:` included.
+        let lower = s.trim().to_lowercase();
+        match lower.as_str() {
+            "easy" => return Ok(Difficulty::Easy),
+            "normal" => return Ok(Difficulty::Normal),
+            "hard" => return Ok(Difficulty::Hard),
+            _ => {}
+        }
+
+        let spec = match lower.strip_prefix("custom:") {
+            Some(spec) => spec,
+            None => {
+                return Err(format!(
+                    "unknown difficulty '{}' (expected easy, normal, hard or custom:LOW..HIGH:GUESSES)",
+                    s
+                ))
+            }
+        };
+
+        let (range, guesses) = spec
+            .split_once(':')
+            .ok_or_else(|| format!("expected custom:LOW..HIGH:GUESSES, got '{}'", s))?;
+        let (low, high) = range
+            .split_once("..")
+            .ok_or_else(|| format!("expected a range like 1..100, got '{}'", range))?;
+
+        let low = parse_number(low)?;
+        let high = parse_number(high)?;
+        let max_guesses = parse_number(guesses)?;
+
+        Difficulty::custom(low, high, max_guesses)
+    }
+}
+
+fn parse_number<T: FromStr>(s: &str) -> Result<T, String> {
+    s.trim()
+        .parse()
+        .map_err(|_| format!("'{}' is not a whole number", s.trim()))
+}
+
+// Asks the player to pick a level when none was given on the command line.
+
+pub fn menu<R: BufRead, W: Write>(input: &mut R, output: &mut W) -> io::Result<Difficulty> {
+    let presets = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    loop {
+        writeln!(output, "Choose a difficulty:")?;
+        for (i, level) in presets.iter().enumerate() {
+            writeln!(output, "  {}) {}", i + 1, level)?;
+        }
+        writeln!(output, "  4) custom")?;
+
+        let choice = match read_line(input)? {
+            Some(line) => line,
+            None => return Ok(Difficulty::default()),
+        };
+
+        match choice.as_str() {
+            "1" | "2" | "3" => {
+                let index: usize = choice.parse().unwrap();
+                return Ok(presets[index - 1]);
+            }
+            "4" => {
+                writeln!(output, "Enter a range and guess limit, e.g. 1..500:9")?;
+                let spec = read_line(input)?.unwrap_or_default();
+                match format!("custom:{}", spec).parse() {
+                    Ok(level) => return Ok(level),
+                    Err(e) => writeln!(output, "That won't work: {}", e)?,
+                }
+            }
+            other => match other.parse() {
+                Ok(level) => return Ok(level),
+                Err(_) => writeln!(output, "Please pick 1, 2, 3 or 4.")?,
+            },
+        }
+    }
+}
+
+// Reads one trimmed line, or `None` once the input has run dry.
+
+fn read_line<R: BufRead>(input: &mut R) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(line.trim().to_string()))
+}
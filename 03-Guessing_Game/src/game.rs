@@ -0,0 +1,403 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+use rand::distributions::Uniform;
+use rand::Rng;
+
+use crate::difficulty::Difficulty;
+use crate::hints::Temperature;
+use crate::input;
+use crate::number::Number;
+
+// The rules of one round: where the secret is picked from, how many guesses
+// the player gets, and how close counts as correct. Whole-number games use a
+// tolerance of zero; float games need some slack or nobody would ever win.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rules<N> {
+    pub low: N,
+    pub high: N,
+    pub max_guesses: u32,
+    pub tolerance: N,
+}
+
+impl<N: Number> Rules<N> {
+    pub fn new(low: N, high: N, max_guesses: u32, tolerance: N) -> Result<Rules<N>, String> {
+        if !low.is_finite() || !high.is_finite() || !tolerance.is_finite() {
+            return Err(String::from("the range and tolerance have to be finite numbers"));
+        }
+        if low > high {
+            return Err(format!("the range {}..{} is empty", low, high));
+        }
+        if max_guesses == 0 {
+            return Err(String::from("you need at least one guess"));
+        }
+        if tolerance < N::zero() {
+            return Err(format!("the tolerance can't be negative, got {}", tolerance));
+        }
+
+        Ok(Rules {
+            low,
+            high,
+            max_guesses,
+            tolerance,
+        })
+    }
+
+    // The range and guess limit of a difficulty level, in some other number type.
+    pub fn from_difficulty(difficulty: Difficulty, tolerance: N) -> Result<Rules<N>, String> {
+        let convert = |bound: i64| {
+            N::from_i64(bound).ok_or_else(|| format!("{} doesn't fit in this kind of number", bound))
+        };
+
+        Rules::new(
+            convert(difficulty.low())?,
+            convert(difficulty.high())?,
+            difficulty.max_guesses(),
+            tolerance,
+        )
+    }
+}
+
+// How a round of the game ended.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome<N = i64> {
+    Won { attempts: u32 },
+    Lost { secret: N },
+    Quit { secret: N },
+}
+
+impl<N> Outcome<N> {
+    // How many guesses it took, if the player won.
+    pub fn won_in(&self) -> Option<u32> {
+        match self {
+            Outcome::Won { attempts } => Some(*attempts),
+            _ => None,
+        }
+    }
+}
+
+// Why a line of input couldn't be used as a guess.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuessError<N = i64> {
+    NotANumber(String),
+    OutOfRange { guess: N, low: N, high: N },
+    Empty,
+}
+
+impl<N: fmt::Display> fmt::Display for GuessError<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GuessError::NotANumber(input) => {
+                write!(f, "'{}' isn't a number. Please type a number.", input)
+            }
+            GuessError::OutOfRange { guess, low, high } => write!(
+                f,
+                "{} is out of range. The number is between {} and {}.",
+                guess, low, high
+            ),
+            GuessError::Empty => write!(f, "You didn't type anything. Please type a number."),
+        }
+    }
+}
+
+impl<N: fmt::Debug + fmt::Display> Error for GuessError<N> {}
+
+// What the player is told when the input runs out (Ctrl-D) mid-game.
+
+pub const OUT_OF_INPUT: &str = "No more input, so that's the end of the game.";
+
+// Turns one line of input into a guess, checking it against the range the
+// secret number was picked from.
+
+pub fn parse_guess<N: Number>(line: &str, rules: Rules<N>) -> Result<N, GuessError<N>> {
+    let line = line.trim();
+    if line.is_empty() {
+        return Err(GuessError::Empty);
+    }
+
+    let guess: N = match line.parse() {
+        Ok(num) if Number::is_finite(num) => num,
+        _ => return Err(GuessError::NotANumber(line.to_string())),
+    };
+
+    if guess < rules.low || guess > rules.high {
+        return Err(GuessError::OutOfRange {
+            guess,
+            low: rules.low,
+            high: rules.high,
+        });
+    }
+
+    Ok(guess)
+}
+
+// The state of a single round: the secret number and the guesses used so far.
+
+#[derive(Debug)]
+pub struct Game<N = i64> {
+    rules: Rules<N>,
+    secret: N,
+    attempts: u32,
+    history: Vec<(N, Ordering)>,
+    // What the secret could still be, judging by the guesses so far.
+    low: N,
+    high: N,
+}
+
+// A guess the player could have known was wrong before making it.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Warning<N = i64> {
+    Repeated(N),
+    AlreadyExcluded { guess: N, low: N, high: N },
+}
+
+impl<N: fmt::Display> fmt::Display for Warning<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Warning::Repeated(guess) => write!(f, "You already guessed {}!", guess),
+            Warning::AlreadyExcluded { guess, low, high } => write!(
+                f,
+                "You already know it's between {} and {}, so it can't be {}.",
+                low, high, guess
+            ),
+        }
+    }
+}
+
+// The classic whole-number game, straight from a difficulty level.
+
+impl Game {
+    pub fn new<R: Rng>(difficulty: Difficulty, rng: &mut R) -> Game {
+        Game::from_rules(difficulty.rules(), rng)
+    }
+
+    pub fn with_secret(difficulty: Difficulty, secret: i64) -> Game {
+        Game::from_rules_with_secret(difficulty.rules(), secret)
+    }
+}
+
+impl<N: Number> Game<N> {
+    pub fn from_rules<R: Rng>(rules: Rules<N>, rng: &mut R) -> Game<N> {
+        let secret = rng.sample(Uniform::new_inclusive(rules.low, rules.high));
+        Game::from_rules_with_secret(rules, secret)
+    }
+
+    pub fn from_rules_with_secret(rules: Rules<N>, secret: N) -> Game<N> {
+        Game {
+            rules,
+            secret,
+            attempts: 0,
+            history: Vec::new(),
+            low: rules.low,
+            high: rules.high,
+        }
+    }
+
+    pub fn rules(&self) -> Rules<N> {
+        self.rules
+    }
+
+    pub fn secret(&self) -> N {
+        self.secret
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn guesses_left(&self) -> u32 {
+        self.rules.max_guesses.saturating_sub(self.attempts)
+    }
+
+    // Every guess so far, with how it compared to the secret number.
+    pub fn history(&self) -> &[(N, Ordering)] {
+        &self.history
+    }
+
+    // The smallest range the secret must be in, given the guesses so far.
+    pub fn possible(&self) -> (N, N) {
+        (self.low, self.high)
+    }
+
+    // Checks a guess against what the player should already know, without
+    // using it up.
+    pub fn warning(&self, guess: N) -> Option<Warning<N>> {
+        if self.history.iter().any(|&(previous, _)| previous == guess) {
+            Some(Warning::Repeated(guess))
+        } else if guess < self.low || guess > self.high {
+            Some(Warning::AlreadyExcluded {
+                guess,
+                low: self.low,
+                high: self.high,
+            })
+        } else {
+            None
+        }
+    }
+
+    // Uses up one guess and says how it compares to the secret number.
+    pub fn guess(&mut self, guess: N) -> Ordering {
+        self.attempts += 1;
+
+        let tolerance = self.rules.tolerance;
+        let ordering = guess.compare(self.secret, tolerance);
+        self.history.push((guess, ordering));
+
+        match ordering {
+            Ordering::Less => {
+                let low = (guess + tolerance).next_up();
+                if low > self.low {
+                    self.low = low;
+                }
+            }
+            Ordering::Greater => {
+                let high = (guess - tolerance).next_down();
+                if high < self.high {
+                    self.high = high;
+                }
+            }
+            Ordering::Equal => {
+                self.low = guess;
+                self.high = guess;
+            }
+        }
+
+        ordering
+    }
+
+    pub fn temperature(&self, guess: N) -> Temperature {
+        Temperature::of(guess, self.secret, self.rules)
+    }
+}
+
+// Plays one round, reading guesses from `input` and writing the transcript
+// to `output`. The secret number is picked with `rng`.
+
+pub fn play<N, R, I, O>(
+    rules: Rules<N>,
+    rng: &mut R,
+    input: &mut I,
+    output: &mut O,
+) -> io::Result<Outcome<N>>
+where
+    N: Number,
+    R: Rng,
+    I: BufRead,
+    O: Write,
+{
+    let mut game = Game::from_rules(rules, rng);
+
+    write!(
+        output,
+        "I'm thinking of a number between {} and {}. You have {} guesses.",
+        rules.low, rules.high, rules.max_guesses
+    )?;
+    if rules.tolerance > N::zero() {
+        write!(output, " Anything within {} of it counts.", rules.tolerance)?;
+    }
+    writeln!(output, "\n")?;
+
+    loop {
+        // The lose condition: out of guesses, so the number is revealed.
+        if game.guesses_left() == 0 {
+            writeln!(output, "Out of guesses! The number was {}.", game.secret())?;
+            return Ok(Outcome::Lost {
+                secret: game.secret(),
+            });
+        }
+
+        match turn(&mut game, "Please to be inputting your guestimation:", input, output)? {
+            Some(Ordering::Equal) => {
+                return Ok(Outcome::Won {
+                    attempts: game.attempts(),
+                })
+            }
+            Some(_) => {}
+            None => {
+                writeln!(output, "{} The number was {}.", OUT_OF_INPUT, game.secret())?;
+                return Ok(Outcome::Quit {
+                    secret: game.secret(),
+                });
+            }
+        }
+
+        let left = game.guesses_left();
+        if left > 0 {
+            writeln!(output, "{} guesses left.", left)?;
+        }
+    }
+}
+
+// One guess: prompts until the input holds a usable guess, then judges it and
+// prints the feedback. Returns `None` if the input runs out (Ctrl-D) first.
+
+pub fn turn<N, I, O>(game: &mut Game<N>, prompt: &str, input: &mut I, output: &mut O) -> io::Result<Option<Ordering>>
+where
+    N: Number,
+    I: BufRead,
+    O: Write,
+{
+    // Bad guesses don't cost the player anything; they're told what was
+    // wrong and asked again.
+    let rules = game.rules();
+    let guess = match input::read_with(input, output, prompt, |line| parse_guess(line, rules))? {
+        Some(guess) => guess,
+        None => return Ok(None),
+    };
+
+    writeln!(output, "You guessed: {}", guess)?;
+
+    // A wasted guess still counts, but at least say so.
+    if let Some(warning) = game.warning(guess) {
+        writeln!(output, "{}", warning)?;
+    }
+
+    let ordering = game.guess(guess);
+    writeln!(output, "{}", feedback(ordering))?;
+
+    if ordering != Ordering::Equal {
+        writeln!(output, "{}", game.temperature(guess))?;
+        write_history(game, output)?;
+    }
+
+    Ok(Some(ordering))
+}
+
+// What the player is told about a guess, given how it compared to the secret.
+
+pub fn feedback(ordering: Ordering) -> &'static str {
+    match ordering {
+        Ordering::Less => "Too low!",
+        Ordering::Greater => "Too high!",
+        Ordering::Equal => "Wow, you got it!",
+    }
+}
+
+// e.g. "Guesses so far: 50 (too high), 25 (too low). It's between 26 and 49."
+
+fn write_history<N: Number, O: Write>(game: &Game<N>, output: &mut O) -> io::Result<()> {
+    let guesses: Vec<String> = game
+        .history()
+        .iter()
+        .map(|&(guess, ordering)| match ordering {
+            Ordering::Less => format!("{} (too low)", guess),
+            Ordering::Greater => format!("{} (too high)", guess),
+            Ordering::Equal => format!("{} (right)", guess),
+        })
+        .collect();
+
+    let (low, high) = game.possible();
+    writeln!(
+        output,
+        "Guesses so far: {}. It's between {} and {}.",
+        guesses.join(", "),
+        low,
+        high
+    )
+}
@@ -0,0 +1,55 @@
+use std::fmt;
+
+use crate::game::Rules;
+use crate::number::Number;
+
+// How close a wrong guess was, measured as a share of the whole range so the
+// hints mean the same thing on easy and on hard.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Temperature {
+    Scorching,
+    Hot,
+    Warm,
+    Cool,
+    Cold,
+    Freezing,
+}
+
+impl Temperature {
+    pub fn of<N: Number>(guess: N, secret: N, rules: Rules<N>) -> Temperature {
+        let distance = guess.distance(secret);
+        let size = N::span(rules.low, rules.high);
+
+        // Percentages of the range, checked from closest to furthest.
+        let bands = [
+            (2.0, Temperature::Scorching),
+            (5.0, Temperature::Hot),
+            (10.0, Temperature::Warm),
+            (25.0, Temperature::Cool),
+            (50.0, Temperature::Cold),
+        ];
+
+        for &(percent, temperature) in bands.iter() {
+            if distance * 100.0 <= size * percent {
+                return temperature;
+            }
+        }
+
+        Temperature::Freezing
+    }
+}
+
+impl fmt::Display for Temperature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let hint = match self {
+            Temperature::Scorching => "Scorching! You're practically touching it.",
+            Temperature::Hot => "Hot!",
+            Temperature::Warm => "Warm.",
+            Temperature::Cool => "Cool.",
+            Temperature::Cold => "Cold.",
+            Temperature::Freezing => "Freezing! Not even close.",
+        };
+        write!(f, "{}", hint)
+    }
+}
@@ -0,0 +1,53 @@
+use std::fmt::Display;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+// The `read_line` + `trim().parse()` dance from the original game, but instead
+// of silently skipping bad input it says what was wrong and asks again.
+// Returns `None` once the input runs out (e.g. Ctrl-D).
+
+pub fn read_parsed<T, I, O>(
+    input: &mut I,
+    output: &mut O,
+    prompt: &str,
+    complaint: &str,
+) -> io::Result<Option<T>>
+where
+    T: FromStr,
+    I: BufRead,
+    O: Write,
+{
+    read_with(input, output, prompt, |line| line.parse::<T>().map_err(|_| complaint))
+}
+
+// The same loop with the parsing handed over to `parse`, which gets the
+// trimmed line and says what's wrong with it, so the complaint can depend on
+// the input.
+
+pub fn read_with<T, E, F, I, O>(
+    input: &mut I,
+    output: &mut O,
+    prompt: &str,
+    mut parse: F,
+) -> io::Result<Option<T>>
+where
+    E: Display,
+    F: FnMut(&str) -> Result<T, E>,
+    I: BufRead,
+    O: Write,
+{
+    loop {
+        writeln!(output, "{}", prompt)?;
+
+        let mut line = String::new();
+
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        match parse(line.trim()) {
+            Ok(value) => return Ok(Some(value)),
+            Err(complaint) => writeln!(output, "{}", complaint)?,
+        }
+    }
+}
@@ -0,0 +1,14 @@
+// The guessing game from chapter 2, split into a library so the game loop can
+// be driven by any random number generator and any input/output handles.
+
+pub mod cli;
+pub mod difficulty;
+pub mod game;
+pub mod hints;
+pub mod input;
+pub mod multiplayer;
+pub mod net;
+pub mod number;
+pub mod reverse;
+pub mod scores;
+pub mod solver;
@@ -1,39 +1,210 @@
-use std::io;
-use rand::Rng;
-use std::cmp::Ordering;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use rand::rngs::StdRng;
+use rand::{FromEntropy, RngCore, SeedableRng};
+
+use guessing_game::cli::Options;
+use guessing_game::difficulty::Difficulty;
+use guessing_game::game::{Game, Rules};
+use guessing_game::scores::{Score, ScoreBoard, ScoreError};
+use guessing_game::{difficulty, game, multiplayer, net, reverse, solver};
 
 fn main() {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}", message);
+            process::exit(2);
+        }
+    };
+
+    let scores_file = options.scores_file.clone().or_else(ScoreBoard::default_path);
+
+    if options.show_scores {
+        let board = match &scores_file {
+            Some(path) => load_scores(path),
+            None => ScoreBoard::default(),
+        };
+        board
+            .print_leaderboard(&mut io::stdout())
+            .expect("Failed to print scores");
+        return;
+    }
+
+    // A seed makes the secret number, and so the whole session, reproducible.
+    let mut rng: Box<dyn RngCore> = match options.seed {
+        Some(seed) => Box::new(StdRng::seed_from_u64(seed)),
+        None => Box::new(rand::thread_rng()),
+    };
+
+    if let Some(addr) = &options.serve {
+        let listener = TcpListener::bind(addr).unwrap_or_else(|e| {
+            eprintln!("Couldn't listen on {}: {}", addr, e);
+            process::exit(1);
+        });
+        let rng = match options.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let difficulty = options.difficulty.unwrap_or_default();
+
+        eprintln!("Serving {} games on {}", difficulty, addr);
+        net::serve(listener, difficulty, rng).expect("Server failed");
+        return;
+    }
+
+    if let Some(games) = options.bench {
+        bench(options.difficulty.unwrap_or_default(), games, &mut rng);
+        return;
+    }
+
     println!("\nGuess the number...\n");
 
-    let secret_number = rand::thread_rng().gen_range(1,101);
-    // let secret_number = rand::random::<u128>();
-    // println!("The (not-so) secret number is {}", secret_number);
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    let mut output = io::stdout();
 
-    loop {
-        println!("Please to be inputting your guestimation:");
+    // Over the network the server decides the level and the secret.
+    if let Some(addr) = &options.connect {
+        if let Err(e) = net::connect(addr, &mut input, &mut output) {
+            eprintln!("Lost the connection to {}: {}", addr, e);
+            process::exit(1);
+        }
+        return;
+    }
 
-        let mut guess = String::new();
+    // No level on the command line? Let the player pick one from the menu,
+    // unless nobody is there to pick.
+    let difficulty = match options.difficulty {
+        Some(level) => level,
+        None if options.auto => Difficulty::default(),
+        None => {
+            let level = difficulty::menu(&mut input, &mut output).expect("Failed to read difficulty");
+            println!();
+            level
+        }
+    };
 
-        io::stdin()
-        .read_line(&mut guess)
-        .expect("Failed to read line");
+    if !options.players.is_empty() {
+        multiplayer::play_match(
+            &options.players,
+            options.rounds,
+            difficulty,
+            &mut rng,
+            &mut input,
+            &mut output,
+        )
+        .expect("Failed to play the match");
+        return;
+    }
 
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_) => continue,
+    if options.reverse {
+        reverse::play(difficulty, &mut input, &mut output).expect("Failed to play the game");
+        return;
+    }
+
+    // The computer's games don't go on the high-score table.
+    if options.auto {
+        let mut strategy = solver::by_name(&options.strategy, difficulty, &mut rng)
+            .expect("strategy names are checked when parsing options");
+        let mut game = Game::new(difficulty, &mut rng);
+        solver::play_auto(&mut *strategy, &mut game, &mut output).expect("Failed to play the game");
+        return;
+    }
+
+    // The float game is the same game, only with some slack on what counts
+    // as the right answer.
+    let won_in = if options.float {
+        let rules = Rules::from_difficulty(difficulty, options.epsilon).unwrap_or_else(|e| {
+            eprintln!("Can't play {} with floats: {}", difficulty, e);
+            process::exit(2);
+        });
+        game::play(rules, &mut rng, &mut input, &mut output).map(|outcome| outcome.won_in())
+    } else {
+        game::play(difficulty.rules(), &mut rng, &mut input, &mut output).map(|outcome| outcome.won_in())
+    };
+
+    if let Some(attempts) = won_in.expect("Failed to play the game") {
+        let path = match scores_file {
+            Some(path) => path,
+            None => {
+                eprintln!("No data directory found, so this score can't be saved.");
+                return;
+            }
         };
-        
-        // ("Please type a number");
 
-        println!("You guessed: {}", guess);
+        let name = match options.name {
+            Some(name) => name,
+            None => ask_name(&mut input).expect("Failed to read name"),
+        };
 
-        match guess.cmp(&secret_number) {
-            Ordering::Less => println!("Too low!"),
-            Ordering::Greater => println!("Too high!"),
-            Ordering::Equal => {
-                println!("Wow, you got it!");
-                break;
+        let mut board = load_scores(&path);
+        board.record(Score::new(&name, attempts, difficulty));
+        if let Err(e) = board.save(&path) {
+            eprintln!("Couldn't save your score: {}", e);
+        }
+    }
+}
+
+// Lets every strategy play the same number of games and compares how many
+// guesses they needed.
+
+fn bench<R: RngCore>(difficulty: Difficulty, games: u32, rng: &mut R) {
+    println!("Benchmarking on {}\n", difficulty);
+
+    for name in solver::STRATEGIES.iter() {
+        let report = solver::benchmark(difficulty, games, rng, |rng| {
+            solver::by_name(name, difficulty, rng).expect("every listed strategy exists")
+        });
+        println!("{}", report);
+    }
+
+    println!(
+        "\nA binary search never needs more than {} guesses for {} numbers.",
+        solver::binary_search_bound(difficulty),
+        i128::from(difficulty.high()) - i128::from(difficulty.low()) + 1
+    );
+}
+
+fn ask_name<R: BufRead>(input: &mut R) -> io::Result<String> {
+    print!("Enter your name for the high-score table: ");
+    io::stdout().flush()?;
+
+    let mut name = String::new();
+    input.read_line(&mut name)?;
+
+    match name.trim() {
+        "" => Ok(String::from("anonymous")),
+        name => Ok(name.to_string()),
+    }
+}
+
+// A broken score file shouldn't stop anyone playing. It is moved out of the
+// way (rather than overwritten) and the game carries on with an empty table.
+
+fn load_scores(path: &Path) -> ScoreBoard {
+    match ScoreBoard::load(path) {
+        Ok(board) => board,
+        Err(ScoreError::Corrupt(e)) => {
+            let backup: PathBuf = path.with_extension("json.corrupt");
+            eprintln!(
+                "Warning: {} is corrupt ({}). Moving it to {} and starting a new table.",
+                path.display(),
+                e,
+                backup.display()
+            );
+            if let Err(e) = fs::rename(path, &backup) {
+                eprintln!("Warning: couldn't move the corrupt file: {}", e);
             }
+            ScoreBoard::default()
+        }
+        Err(e) => {
+            eprintln!("Warning: {}", e);
+            ScoreBoard::default()
         }
     }
 }
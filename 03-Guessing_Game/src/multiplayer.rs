@@ -0,0 +1,200 @@
+use std::cmp::Ordering;
+use std::io::{self, BufRead, Write};
+
+use rand::Rng;
+
+use crate::difficulty::Difficulty;
+use crate::game::{self, Game};
+
+// Hot-seat play: several people share one keyboard and take turns guessing the
+// same secret number. Whoever gets it wins the round, and the match is
+// best-of-`rounds`.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Player {
+    pub name: String,
+    pub rounds_won: u32,
+    pub attempts: u32,
+}
+
+impl Player {
+    pub fn new(name: &str) -> Player {
+        Player {
+            name: name.to_string(),
+            rounds_won: 0,
+            attempts: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchResult {
+    pub players: Vec<Player>,
+    pub rounds_played: u32,
+    pub finished: bool,
+}
+
+impl MatchResult {
+    // Most rounds won takes it, with fewer guesses overall breaking a tie.
+    // `None` means a draw.
+    pub fn winner(&self) -> Option<&Player> {
+        let mut ranked: Vec<&Player> = self.players.iter().collect();
+        ranked.sort_by(|a, b| {
+            b.rounds_won
+                .cmp(&a.rounds_won)
+                .then_with(|| a.attempts.cmp(&b.attempts))
+        });
+
+        match (ranked.first(), ranked.get(1)) {
+            (Some(first), Some(second))
+                if first.rounds_won == second.rounds_won && first.attempts == second.attempts =>
+            {
+                None
+            }
+            (Some(first), _) if first.rounds_won > 0 => Some(first),
+            _ => None,
+        }
+    }
+
+    pub fn print_summary<O: Write>(&self, output: &mut O) -> io::Result<()> {
+        writeln!(output, "\nAfter {} round(s):\n", self.rounds_played)?;
+        writeln!(output, "{:<16} {:>10} {:>8}", "Player", "Rounds won", "Guesses")?;
+
+        for player in &self.players {
+            writeln!(
+                output,
+                "{:<16} {:>10} {:>8}",
+                player.name, player.rounds_won, player.attempts
+            )?;
+        }
+
+        match self.winner() {
+            Some(player) => writeln!(output, "\n{} wins the match!", player.name),
+            None => writeln!(output, "\nIt's a draw."),
+        }
+    }
+}
+
+pub fn play_match<R, I, O>(
+    names: &[String],
+    rounds: u32,
+    difficulty: Difficulty,
+    rng: &mut R,
+    input: &mut I,
+    output: &mut O,
+) -> io::Result<MatchResult>
+where
+    R: Rng,
+    I: BufRead,
+    O: Write,
+{
+    let mut result = MatchResult {
+        players: names.iter().map(|name| Player::new(name)).collect(),
+        rounds_played: 0,
+        finished: true,
+    };
+
+    for round in 0..rounds {
+        if decided(&result.players, rounds - round) {
+            break;
+        }
+
+        if round > 0 {
+            writeln!(output)?;
+        }
+        writeln!(
+            output,
+            "Round {} of {}: I'm thinking of a number between {} and {}. Everyone gets {} guesses.\n",
+            round + 1,
+            rounds,
+            difficulty.low(),
+            difficulty.high(),
+            difficulty.max_guesses()
+        )?;
+
+        // The first turn moves round the table from one round to the next.
+        let first = round as usize % result.players.len();
+
+        if !play_round(&mut result.players, first, difficulty, rng, input, output)? {
+            writeln!(output, "{}", game::OUT_OF_INPUT)?;
+            result.finished = false;
+            break;
+        }
+
+        result.rounds_played += 1;
+    }
+
+    result.print_summary(output)?;
+
+    Ok(result)
+}
+
+// Returns `false` if the input ran out part way through.
+
+fn play_round<R, I, O>(
+    players: &mut [Player],
+    first: usize,
+    difficulty: Difficulty,
+    rng: &mut R,
+    input: &mut I,
+    output: &mut O,
+) -> io::Result<bool>
+where
+    R: Rng,
+    I: BufRead,
+    O: Write,
+{
+    let mut game = Game::new(difficulty, rng);
+    let mut used = vec![0; players.len()];
+
+    loop {
+        if used.iter().all(|&n| n >= difficulty.max_guesses()) {
+            writeln!(
+                output,
+                "Everyone is out of guesses! The number was {}.",
+                game.secret()
+            )?;
+            return Ok(true);
+        }
+
+        for offset in 0..players.len() {
+            let index = (first + offset) % players.len();
+            if used[index] >= difficulty.max_guesses() {
+                continue;
+            }
+
+            let prompt = format!(
+                "{}, please to be inputting your guestimation ({} left):",
+                players[index].name,
+                difficulty.max_guesses() - used[index]
+            );
+
+            let ordering = match game::turn(&mut game, &prompt, input, output)? {
+                Some(ordering) => ordering,
+                None => return Ok(false),
+            };
+
+            used[index] += 1;
+            players[index].attempts += 1;
+
+            if ordering == Ordering::Equal {
+                players[index].rounds_won += 1;
+                writeln!(output, "{} takes the round!", players[index].name)?;
+                return Ok(true);
+            }
+        }
+    }
+}
+
+// Whether someone is so far ahead that the remaining rounds can't change the
+// result.
+
+fn decided(players: &[Player], rounds_left: u32) -> bool {
+    let mut wins: Vec<u32> = players.iter().map(|p| p.rounds_won).collect();
+    wins.sort_unstable_by(|a, b| b.cmp(a));
+
+    match (wins.first(), wins.get(1)) {
+        (Some(&first), Some(&second)) => first > second + rounds_left,
+        _ => false,
+    }
+}
@@ -0,0 +1,294 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::str::FromStr;
+use std::thread;
+
+use rand::rngs::StdRng;
+
+use crate::difficulty::Difficulty;
+use crate::game::{self, Game, Outcome};
+
+// The guessing game over TCP. Each connection gets its own secret number and
+// talks a line protocol:
+//
+//   server: HELLO <low> <high> <guesses>
+//   client: GUESS <n>
+//   server: LOW | HIGH | WIN <attempts> | LOSE <secret> | ERR <message>
+//   client: QUIT
+//   server: BYE <secret>
+//
+// LOW and HIGH describe the guess, just like "Too low!" and "Too high!".
+// The connection is closed after WIN, LOSE or BYE.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerMessage {
+    Hello { low: i64, high: i64, guesses: u32 },
+    Low,
+    High,
+    Win(u32),
+    Lose(i64),
+    Bye(i64),
+    Error(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientMessage {
+    // Kept as text so the server can say exactly what was wrong with it.
+    Guess(String),
+    Quit,
+}
+
+impl fmt::Display for ServerMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ServerMessage::Hello { low, high, guesses } => write!(f, "HELLO {} {} {}", low, high, guesses),
+            ServerMessage::Low => write!(f, "LOW"),
+            ServerMessage::High => write!(f, "HIGH"),
+            ServerMessage::Win(attempts) => write!(f, "WIN {}", attempts),
+            ServerMessage::Lose(secret) => write!(f, "LOSE {}", secret),
+            ServerMessage::Bye(secret) => write!(f, "BYE {}", secret),
+            ServerMessage::Error(message) => write!(f, "ERR {}", message),
+        }
+    }
+}
+
+impl FromStr for ServerMessage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (command, rest) = s.split_once(' ').unwrap_or((s, ""));
+
+        fn number<T: FromStr>(text: &str, message: &str) -> Result<T, String> {
+            text.trim()
+                .parse()
+                .map_err(|_| format!("bad number in '{}'", message))
+        }
+
+        match command {
+            "HELLO" => {
+                let fields: Vec<&str> = rest.split_whitespace().collect();
+                if fields.len() != 3 {
+                    return Err(format!("expected HELLO <low> <high> <guesses>, got '{}'", s));
+                }
+                Ok(ServerMessage::Hello {
+                    low: number(fields[0], s)?,
+                    high: number(fields[1], s)?,
+                    guesses: number(fields[2], s)?,
+                })
+            }
+            "LOW" => Ok(ServerMessage::Low),
+            "HIGH" => Ok(ServerMessage::High),
+            "WIN" => Ok(ServerMessage::Win(number(rest, s)?)),
+            "LOSE" => Ok(ServerMessage::Lose(number(rest, s)?)),
+            "BYE" => Ok(ServerMessage::Bye(number(rest, s)?)),
+            "ERR" => Ok(ServerMessage::Error(rest.to_string())),
+            _ => Err(format!("unknown message '{}'", s)),
+        }
+    }
+}
+
+impl fmt::Display for ClientMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClientMessage::Guess(guess) => write!(f, "GUESS {}", guess),
+            ClientMessage::Quit => write!(f, "QUIT"),
+        }
+    }
+}
+
+impl FromStr for ClientMessage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (command, rest) = s.split_once(' ').unwrap_or((s, ""));
+
+        match command.to_uppercase().as_str() {
+            "GUESS" => Ok(ClientMessage::Guess(rest.trim().to_string())),
+            "QUIT" => Ok(ClientMessage::Quit),
+            _ => Err(format!("unknown command '{}', expected GUESS <n> or QUIT", command)),
+        }
+    }
+}
+
+fn send<W: Write, M: fmt::Display>(writer: &mut W, message: M) -> io::Result<()> {
+    writeln!(writer, "{}", message)?;
+    writer.flush()
+}
+
+// Runs the server side of one game. It only needs something to read lines
+// from and something to write lines to, so it works just as well on a
+// `TcpStream` as on an in-memory buffer.
+
+pub fn session<R: BufRead, W: Write>(mut game: Game, reader: &mut R, writer: &mut W) -> io::Result<Outcome> {
+    let rules = game.rules();
+
+    send(
+        writer,
+        ServerMessage::Hello {
+            low: rules.low,
+            high: rules.high,
+            guesses: rules.max_guesses,
+        },
+    )?;
+
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(Outcome::Quit {
+                secret: game.secret(),
+            });
+        }
+
+        let text = match line.parse() {
+            Ok(ClientMessage::Guess(text)) => text,
+            Ok(ClientMessage::Quit) => {
+                send(writer, ServerMessage::Bye(game.secret()))?;
+                return Ok(Outcome::Quit {
+                    secret: game.secret(),
+                });
+            }
+            Err(e) => {
+                send(writer, ServerMessage::Error(e))?;
+                continue;
+            }
+        };
+
+        let guess = match game::parse_guess(&text, rules) {
+            Ok(guess) => guess,
+            Err(e) => {
+                send(writer, ServerMessage::Error(e.to_string()))?;
+                continue;
+            }
+        };
+
+        match game.guess(guess) {
+            Ordering::Equal => {
+                send(writer, ServerMessage::Win(game.attempts()))?;
+                return Ok(Outcome::Won {
+                    attempts: game.attempts(),
+                });
+            }
+            _ if game.guesses_left() == 0 => {
+                send(writer, ServerMessage::Lose(game.secret()))?;
+                return Ok(Outcome::Lost {
+                    secret: game.secret(),
+                });
+            }
+            Ordering::Less => send(writer, ServerMessage::Low)?,
+            Ordering::Greater => send(writer, ServerMessage::High)?,
+        }
+    }
+}
+
+// Accepts connections forever, one thread per client. Each secret is drawn
+// from `rng` before its connection's thread starts, so a seeded server hands
+// out a reproducible sequence of numbers.
+
+pub fn serve(listener: TcpListener, difficulty: Difficulty, mut rng: StdRng) -> io::Result<()> {
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("Failed to accept a connection: {}", e);
+                continue;
+            }
+        };
+
+        let game = Game::new(difficulty, &mut rng);
+
+        thread::spawn(move || {
+            let peer = stream
+                .peer_addr()
+                .map(|addr| addr.to_string())
+                .unwrap_or_else(|_| String::from("unknown client"));
+
+            match handle(stream, game) {
+                Ok(outcome) => eprintln!("{}: {:?}", peer, outcome),
+                Err(e) => eprintln!("{}: {}", peer, e),
+            }
+        });
+    }
+
+    Ok(())
+}
+
+fn handle(stream: TcpStream, game: Game) -> io::Result<Outcome> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+    session(game, &mut reader, &mut writer)
+}
+
+// The terminal side: reads guesses from the player and shows the server's
+// answers the way the local game would.
+
+pub fn connect<A, I, O>(addr: A, input: &mut I, output: &mut O) -> io::Result<Outcome>
+where
+    A: ToSocketAddrs,
+    I: BufRead,
+    O: Write,
+{
+    let stream = TcpStream::connect(addr)?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+    client(&mut reader, &mut writer, input, output)
+}
+
+pub fn client<R, W, I, O>(server_in: &mut R, server_out: &mut W, input: &mut I, output: &mut O) -> io::Result<Outcome>
+where
+    R: BufRead,
+    W: Write,
+    I: BufRead,
+    O: Write,
+{
+    if let ServerMessage::Hello { low, high, guesses } = receive(server_in)? {
+        writeln!(
+            output,
+            "Connected! I'm thinking of a number between {} and {}. You have {} guesses.\n",
+            low, high, guesses
+        )?;
+    }
+
+    loop {
+        writeln!(output, "Please to be inputting your guestimation:")?;
+
+        let mut guess = String::new();
+        if input.read_line(&mut guess)? == 0 {
+            send(server_out, ClientMessage::Quit)?;
+        } else {
+            send(server_out, ClientMessage::Guess(guess.trim().to_string()))?;
+        }
+
+        match receive(server_in)? {
+            ServerMessage::Low => writeln!(output, "{}", game::feedback(Ordering::Less))?,
+            ServerMessage::High => writeln!(output, "{}", game::feedback(Ordering::Greater))?,
+            ServerMessage::Win(attempts) => {
+                writeln!(output, "{}", game::feedback(Ordering::Equal))?;
+                return Ok(Outcome::Won { attempts });
+            }
+            ServerMessage::Lose(secret) => {
+                writeln!(output, "Out of guesses! The number was {}.", secret)?;
+                return Ok(Outcome::Lost { secret });
+            }
+            ServerMessage::Bye(secret) => {
+                writeln!(output, "{} The number was {}.", game::OUT_OF_INPUT, secret)?;
+                return Ok(Outcome::Quit { secret });
+            }
+            ServerMessage::Error(message) => writeln!(output, "{}", message)?,
+            ServerMessage::Hello { .. } => {}
+        }
+    }
+}
+
+fn receive<R: BufRead>(reader: &mut R) -> io::Result<ServerMessage> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "the server hung up"));
+    }
+
+    line.parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
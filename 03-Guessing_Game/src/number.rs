@@ -0,0 +1,128 @@
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display};
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+
+use rand::distributions::uniform::SampleUniform;
+
+// Anything the game can use as a secret number. Parsing comes from `FromStr`
+// and comparing goes through `compare`, so the game itself never needs to
+// know whether it's playing with whole numbers, negative numbers or floats.
+
+pub trait Number:
+    Copy + PartialOrd + Display + Debug + FromStr + SampleUniform + Add<Output = Self> + Sub<Output = Self>
+{
+    fn zero() -> Self;
+
+    // Converts one of the `Difficulty` bounds, if it fits.
+    fn from_i64(value: i64) -> Option<Self>;
+
+    // Rules out things like NaN and infinity, which parse but aren't guesses.
+    fn is_finite(self) -> bool;
+
+    // How `self` (the guess) compares to `secret`. Anything within
+    // `tolerance` of the secret counts as `Equal`.
+    fn compare(self, secret: Self, tolerance: Self) -> Ordering;
+
+    fn distance(self, other: Self) -> f64;
+
+    // How many values `low..=high` covers, as used by the hot/cold hints.
+    fn span(low: Self, high: Self) -> f64;
+
+    // The nearest values worth guessing either side of `self`. Whole numbers
+    // step by one; floats have no "next" value, so they stay put.
+    fn next_up(self) -> Self;
+    fn next_down(self) -> Self;
+}
+
+macro_rules! integer_number {
+    ($($t:ty),*) => {$(
+        impl Number for $t {
+            fn zero() -> Self {
+                0
+            }
+
+            fn from_i64(value: i64) -> Option<Self> {
+                use std::convert::TryFrom;
+                <$t>::try_from(value).ok()
+            }
+
+            fn is_finite(self) -> bool {
+                true
+            }
+
+            fn compare(self, secret: Self, tolerance: Self) -> Ordering {
+                if self.saturating_add(tolerance) < secret {
+                    Ordering::Less
+                } else if self.saturating_sub(tolerance) > secret {
+                    Ordering::Greater
+                } else {
+                    Ordering::Equal
+                }
+            }
+
+            fn distance(self, other: Self) -> f64 {
+                (self as f64 - other as f64).abs()
+            }
+
+            fn span(low: Self, high: Self) -> f64 {
+                high as f64 - low as f64 + 1.0
+            }
+
+            fn next_up(self) -> Self {
+                self.saturating_add(1)
+            }
+
+            fn next_down(self) -> Self {
+                self.saturating_sub(1)
+            }
+        }
+    )*};
+}
+
+macro_rules! float_number {
+    ($($t:ty),*) => {$(
+        impl Number for $t {
+            fn zero() -> Self {
+                0.0
+            }
+
+            fn from_i64(value: i64) -> Option<Self> {
+                Some(value as $t)
+            }
+
+            fn is_finite(self) -> bool {
+                <$t>::is_finite(self)
+            }
+
+            fn compare(self, secret: Self, tolerance: Self) -> Ordering {
+                if self + tolerance < secret {
+                    Ordering::Less
+                } else if self - tolerance > secret {
+                    Ordering::Greater
+                } else {
+                    Ordering::Equal
+                }
+            }
+
+            fn distance(self, other: Self) -> f64 {
+                (f64::from(self) - f64::from(other)).abs()
+            }
+
+            fn span(low: Self, high: Self) -> f64 {
+                f64::from(high) - f64::from(low)
+            }
+
+            fn next_up(self) -> Self {
+                self
+            }
+
+            fn next_down(self) -> Self {
+                self
+            }
+        }
+    )*};
+}
+
+integer_number!(i32, i64, u32, u64);
+float_number!(f32, f64);
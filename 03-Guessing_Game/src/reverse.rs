@@ -0,0 +1,120 @@
+use std::cmp::Ordering;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+use crate::difficulty::Difficulty;
+use crate::input;
+use crate::solver::{BinarySearch, Strategy};
+
+// The roles swapped: the player thinks of a number and the program guesses,
+// using the same binary search as `--auto`.
+
+// What the player says about the program's guess.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Answer {
+    Higher,
+    Lower,
+    Correct,
+}
+
+impl Answer {
+    // The player's number being higher means the guess was too low, and so on.
+    pub fn ordering(self) -> Ordering {
+        match self {
+            Answer::Higher => Ordering::Less,
+            Answer::Lower => Ordering::Greater,
+            Answer::Correct => Ordering::Equal,
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "h" | "higher" | "+" => Ok(Answer::Higher),
+            "l" | "lower" | "-" => Ok(Answer::Lower),
+            "c" | "correct" | "y" | "yes" | "=" => Ok(Answer::Correct),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReverseOutcome {
+    Guessed { guesses: u32 },
+    Cheated,
+    Quit,
+}
+
+pub fn play<I: BufRead, O: Write>(
+    difficulty: Difficulty,
+    input: &mut I,
+    output: &mut O,
+) -> io::Result<ReverseOutcome> {
+    writeln!(
+        output,
+        "Think of a number between {} and {} and I'll guess it.\n",
+        difficulty.low(),
+        difficulty.high()
+    )?;
+
+    let mut strategy = BinarySearch::new(difficulty);
+    let mut guesses = 0;
+
+    // The tightest things the player has claimed so far, to point at when
+    // their answers stop adding up.
+    let mut above: Option<i64> = None;
+    let mut below: Option<i64> = None;
+
+    loop {
+        if strategy.remaining().is_none() {
+            let accusation = match (above, below) {
+                (Some(a), Some(b)) => format!("You said it's higher than {} but lower than {}.", a, b),
+                (Some(a), None) => format!(
+                    "You said it's higher than {}, but it can't be more than {}.",
+                    a,
+                    difficulty.high()
+                ),
+                (None, Some(b)) => format!(
+                    "You said it's lower than {}, but it can't be less than {}.",
+                    b,
+                    difficulty.low()
+                ),
+                (None, None) => unreachable!("the range only empties after an answer"),
+            };
+            writeln!(output, "{} No number fits that. Cheater!", accusation)?;
+            return Ok(ReverseOutcome::Cheated);
+        }
+
+        let guess = strategy.next_guess();
+        guesses += 1;
+
+        let prompt = format!("Is it {}? (higher/lower/correct)", guess);
+        let answer: Answer = match input::read_parsed(
+            input,
+            output,
+            &prompt,
+            "Please answer higher, lower or correct.",
+        )? {
+            Some(answer) => answer,
+            None => {
+                writeln!(output, "\nGiving up? Fair enough.")?;
+                return Ok(ReverseOutcome::Quit);
+            }
+        };
+
+        match answer {
+            Answer::Correct => {
+                writeln!(output, "Got it in {} guesses!", guesses)?;
+                return Ok(ReverseOutcome::Guessed { guesses });
+            }
+            Answer::Higher => above = Some(guess),
+            Answer::Lower => below = Some(guess),
+        }
+
+        strategy.feedback(guess, answer.ordering());
+    }
+}
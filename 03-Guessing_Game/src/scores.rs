@@ -0,0 +1,161 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::difficulty::Difficulty;
+
+// One finished (and won) game.
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Score {
+    pub name: String,
+    pub attempts: u32,
+    pub difficulty: String,
+    pub low: i64,
+    pub high: i64,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Score {
+    pub fn new(name: &str, attempts: u32, difficulty: Difficulty) -> Score {
+        Score {
+            name: name.to_string(),
+            attempts,
+            difficulty: difficulty.name().to_string(),
+            low: difficulty.low(),
+            high: difficulty.high(),
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+// Things that can go wrong reading or writing the score file.
+
+#[derive(Debug)]
+pub enum ScoreError {
+    Io(io::Error),
+    Corrupt(serde_json::Error),
+}
+
+impl fmt::Display for ScoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScoreError::Io(e) => write!(f, "could not access the score file: {}", e),
+            ScoreError::Corrupt(e) => write!(f, "the score file is corrupt: {}", e),
+        }
+    }
+}
+
+impl Error for ScoreError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ScoreError::Io(e) => Some(e),
+            ScoreError::Corrupt(e) => Some(e),
+        }
+    }
+}
+
+impl From<io::Error> for ScoreError {
+    fn from(e: io::Error) -> Self {
+        ScoreError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for ScoreError {
+    fn from(e: serde_json::Error) -> Self {
+        ScoreError::Corrupt(e)
+    }
+}
+
+// All the recorded scores, stored as JSON.
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ScoreBoard {
+    scores: Vec<Score>,
+}
+
+impl ScoreBoard {
+    // Where the scores live unless the player says otherwise, e.g.
+    // ~/.local/share/guessing_game/scores.json on Linux.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("guessing_game").join("scores.json"))
+    }
+
+    // A missing file is just an empty score board; anything unreadable is an error.
+    pub fn load(path: &Path) -> Result<ScoreBoard, ScoreError> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Ok(serde_json::from_str(&contents)?),
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => Ok(ScoreBoard::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    // Writes to a temporary file first, so a crash half way through never
+    // leaves a truncated score file behind.
+    pub fn save(&self, path: &Path) -> Result<(), ScoreError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        fs::rename(&tmp, path)?;
+
+        Ok(())
+    }
+
+    pub fn record(&mut self, score: Score) {
+        self.scores.push(score);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scores.is_empty()
+    }
+
+    // Fewest attempts first. Ties go to the bigger range, then to whoever got
+    // there first. A custom range can be wider than an i64 holds, so the
+    // widths are worked out as i128.
+    pub fn ranked(&self) -> Vec<&Score> {
+        let width = |score: &Score| i128::from(score.high) - i128::from(score.low);
+        let mut ranked: Vec<&Score> = self.scores.iter().collect();
+        ranked.sort_by(|a, b| {
+            a.attempts
+                .cmp(&b.attempts)
+                .then_with(|| width(b).cmp(&width(a)))
+                .then_with(|| a.timestamp.cmp(&b.timestamp))
+        });
+        ranked
+    }
+
+    pub fn print_leaderboard<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        if self.is_empty() {
+            return writeln!(output, "No high scores yet. Go win a game!");
+        }
+
+        writeln!(
+            output,
+            "{:>4}  {:<16} {:>8}  {:<8} {:<11} When",
+            "Rank", "Name", "Attempts", "Level", "Range"
+        )?;
+
+        for (rank, score) in self.ranked().iter().enumerate() {
+            writeln!(
+                output,
+                "{:>4}  {:<16} {:>8}  {:<8} {:<11} {}",
+                rank + 1,
+                score.name,
+                score.attempts,
+                score.difficulty,
+                format!("{}..{}", score.low, score.high),
+                score.timestamp.format("%Y-%m-%d %H:%M")
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,239 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::io::{self, Write};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::difficulty::Difficulty;
+use crate::game::{self, Game, Outcome};
+
+// A way for the computer to play the guessing game. The strategy proposes a
+// guess and is then told how that guess compared to the secret number, using
+// the same `Ordering` the game itself works out with `guess.cmp(&secret)`.
+
+pub trait Strategy {
+    fn name(&self) -> &'static str;
+
+    fn next_guess(&mut self) -> i64;
+
+    fn feedback(&mut self, guess: i64, ordering: Ordering);
+}
+
+// Always guesses the middle of what's left, halving the range every time.
+// That makes it O(log n): never more than ceil(log2(n + 1)) guesses.
+
+#[derive(Debug)]
+pub struct BinarySearch {
+    low: i64,
+    high: i64,
+}
+
+impl BinarySearch {
+    pub fn new(difficulty: Difficulty) -> BinarySearch {
+        BinarySearch {
+            low: difficulty.low(),
+            high: difficulty.high(),
+        }
+    }
+
+    // The numbers that could still be the secret, or `None` once the feedback
+    // has ruled them all out.
+    pub fn remaining(&self) -> Option<(i64, i64)> {
+        if self.low <= self.high {
+            Some((self.low, self.high))
+        } else {
+            None
+        }
+    }
+}
+
+impl Strategy for BinarySearch {
+    fn name(&self) -> &'static str {
+        "binary search"
+    }
+
+    fn next_guess(&mut self) -> i64 {
+        // Halving the gap rather than averaging the ends can't overflow.
+        self.low + ((i128::from(self.high) - i128::from(self.low)) / 2) as i64
+    }
+
+    fn feedback(&mut self, guess: i64, ordering: Ordering) {
+        narrow(&mut self.low, &mut self.high, guess, ordering);
+    }
+}
+
+// Picks any number that's still possible. It still learns from the feedback,
+// but on average it needs noticeably more guesses than a binary search.
+
+#[derive(Debug)]
+pub struct RandomSearch<R> {
+    low: i64,
+    high: i64,
+    rng: R,
+}
+
+impl<R: Rng> RandomSearch<R> {
+    pub fn new(difficulty: Difficulty, rng: R) -> RandomSearch<R> {
+        RandomSearch {
+            low: difficulty.low(),
+            high: difficulty.high(),
+            rng,
+        }
+    }
+}
+
+impl<R: Rng> Strategy for RandomSearch<R> {
+    fn name(&self) -> &'static str {
+        "random search"
+    }
+
+    fn next_guess(&mut self) -> i64 {
+        self.rng.gen_range(self.low, self.high + 1)
+    }
+
+    fn feedback(&mut self, guess: i64, ordering: Ordering) {
+        narrow(&mut self.low, &mut self.high, guess, ordering);
+    }
+}
+
+// Shrinks the range of possible secrets after a wrong guess. If the feedback
+// contradicts itself the range ends up empty, with `low > high`.
+
+fn narrow(low: &mut i64, high: &mut i64, guess: i64, ordering: Ordering) {
+    match ordering {
+        Ordering::Less => *low = guess.saturating_add(1).max(*low),
+        Ordering::Greater => match guess.checked_sub(1) {
+            Some(below) => *high = below.min(*high),
+            None => {
+                // Nothing is lower than the lowest i64.
+                *low = guess + 1;
+                *high = guess;
+            }
+        },
+        Ordering::Equal => {
+            *low = guess;
+            *high = guess;
+        }
+    }
+}
+
+// Lets a strategy play a normal round, guess limit and all, printing the
+// same transcript a human player would see.
+
+pub fn play_auto<S, O>(strategy: &mut S, game: &mut Game, output: &mut O) -> io::Result<Outcome>
+where
+    S: Strategy + ?Sized,
+    O: Write,
+{
+    writeln!(output, "The computer plays using {}.\n", strategy.name())?;
+
+    loop {
+        if game.guesses_left() == 0 {
+            writeln!(output, "Out of guesses! The number was {}.", game.secret())?;
+            return Ok(Outcome::Lost {
+                secret: game.secret(),
+            });
+        }
+
+        let guess = strategy.next_guess();
+        writeln!(output, "Computer guessed: {}", guess)?;
+
+        let ordering = game.guess(guess);
+        writeln!(output, "{}", game::feedback(ordering))?;
+
+        if ordering == Ordering::Equal {
+            return Ok(Outcome::Won {
+                attempts: game.attempts(),
+            });
+        }
+
+        strategy.feedback(guess, ordering);
+    }
+}
+
+// Counts the guesses a strategy needs to find `secret`, ignoring the guess
+// limit so every game runs to the end.
+
+pub fn solve<S: Strategy + ?Sized>(strategy: &mut S, secret: i64) -> u32 {
+    let mut guesses = 0;
+
+    loop {
+        let guess = strategy.next_guess();
+        guesses += 1;
+
+        match guess.cmp(&secret) {
+            Ordering::Equal => return guesses,
+            ordering => strategy.feedback(guess, ordering),
+        }
+    }
+}
+
+// The result of letting one strategy play many games.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchReport {
+    pub strategy: &'static str,
+    pub games: u32,
+    pub average: f64,
+    pub worst: u32,
+}
+
+impl fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:<14} average {:>6.2} guesses, worst case {:>4} over {} games",
+            self.strategy, self.average, self.worst, self.games
+        )
+    }
+}
+
+pub fn benchmark<R, F>(difficulty: Difficulty, games: u32, rng: &mut R, mut new_strategy: F) -> BenchReport
+where
+    R: Rng,
+    F: FnMut(&mut R) -> Box<dyn Strategy>,
+{
+    let mut name = "";
+    let mut total = 0u64;
+    let mut worst = 0;
+
+    for _ in 0..games {
+        let secret = Game::new(difficulty, rng).secret();
+        let mut strategy = new_strategy(rng);
+        name = strategy.name();
+        let guesses = solve(&mut *strategy, secret);
+
+        total += u64::from(guesses);
+        worst = worst.max(guesses);
+    }
+
+    BenchReport {
+        strategy: name,
+        games,
+        average: if games == 0 { 0.0 } else { total as f64 / f64::from(games) },
+        worst,
+    }
+}
+
+// Builds a strategy by name, as given to `--strategy`.
+
+pub fn by_name<R: Rng>(name: &str, difficulty: Difficulty, rng: &mut R) -> Option<Box<dyn Strategy>> {
+    match name {
+        "binary" => Some(Box::new(BinarySearch::new(difficulty))),
+        "random" => Some(Box::new(RandomSearch::new(
+            difficulty,
+            StdRng::seed_from_u64(rng.gen()),
+        ))),
+        _ => None,
+    }
+}
+
+pub const STRATEGIES: [&str; 2] = ["binary", "random"];
+
+// The most guesses a binary search can ever need over `difficulty`'s range.
+
+pub fn binary_search_bound(difficulty: Difficulty) -> u32 {
+    let size = i128::from(difficulty.high()) - i128::from(difficulty.low()) + 1;
+    128 - size.leading_zeros()
+}
@@ -0,0 +1,18 @@
+use guessing_game::difficulty::Difficulty;
+
+#[test]
+fn levels_print_their_range_the_way_they_are_written() {
+    assert_eq!(Difficulty::Normal.to_string(), "normal (1..100, 7 guesses)");
+
+    let custom = Difficulty::custom(-500, -100, 5).unwrap();
+    assert_eq!(custom.to_string(), "custom (-500..-100, 5 guesses)");
+    assert_eq!("custom:-500..-100:5".parse(), Ok(custom));
+}
+
+#[test]
+fn names_ignore_case() {
+    assert_eq!("Easy".parse(), Ok(Difficulty::Easy));
+    assert_eq!(" HARD ".parse(), Ok(Difficulty::Hard));
+    assert_eq!("CUSTOM:1..5:3".parse(), Difficulty::custom(1, 5, 3));
+    assert_eq!("Custom:-5..5:3".parse(), Difficulty::custom(-5, 5, 3));
+}
@@ -0,0 +1,213 @@
+use std::cmp::Ordering;
+use std::io::Cursor;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use guessing_game::difficulty::Difficulty;
+use guessing_game::game::{self, Game, GuessError, Outcome, Warning};
+use guessing_game::hints::Temperature;
+
+// Runs a whole round with a seeded RNG and a script of guesses, returning the
+// outcome and everything the game printed.
+
+fn run(difficulty: Difficulty, seed: u64, script: &str) -> (Outcome, String) {
+    let mut input = Cursor::new(script.as_bytes().to_vec());
+    let mut output = Vec::new();
+    let outcome = game::play(
+        difficulty.rules(),
+        &mut StdRng::seed_from_u64(seed),
+        &mut input,
+        &mut output,
+    )
+    .unwrap();
+
+    (outcome, String::from_utf8(output).unwrap())
+}
+
+// The same seed always leads to the same secret number.
+
+fn secret_for(difficulty: Difficulty, seed: u64) -> i64 {
+    Game::new(difficulty, &mut StdRng::seed_from_u64(seed)).secret()
+}
+
+#[test]
+fn same_seed_same_secret() {
+    for seed in 0..20 {
+        assert_eq!(
+            secret_for(Difficulty::Hard, seed),
+            secret_for(Difficulty::Hard, seed)
+        );
+    }
+}
+
+#[test]
+fn secret_stays_in_range() {
+    let difficulty = Difficulty::custom(10, 12, 3).unwrap();
+    for seed in 0..100 {
+        let secret = secret_for(difficulty, seed);
+        assert!((10..=12).contains(&secret), "secret {} out of range", secret);
+    }
+}
+
+#[test]
+fn transcript_too_low_too_high_then_win() {
+    let difficulty = Difficulty::custom(1, 100, 7).unwrap();
+    let seed = 42;
+    let secret = secret_for(difficulty, seed);
+    assert!(secret > 1 && secret < 100, "pick a seed with room either side");
+    let (low, high) = (secret - 1, secret + 1);
+
+    let script = format!("{}\n{}\n{}\n", low, high, secret);
+    let (outcome, transcript) = run(difficulty, seed, &script);
+
+    let expected = format!(
+        "I'm thinking of a number between 1 and 100. You have 7 guesses.\n\
+         \n\
+         Please to be inputting your guestimation:\n\
+         You guessed: {low}\n\
+         Too low!\n\
+         Scorching! You're practically touching it.\n\
+         Guesses so far: {low} (too low). It's between {secret} and 100.\n\
+         6 guesses left.\n\
+         Please to be inputting your guestimation:\n\
+         You guessed: {high}\n\
+         Too high!\n\
+         Scorching! You're practically touching it.\n\
+         Guesses so far: {low} (too low), {high} (too high). It's between {secret} and {secret}.\n\
+         5 guesses left.\n\
+         Please to be inputting your guestimation:\n\
+         You guessed: {secret}\n\
+         Wow, you got it!\n",
+        low = low,
+        high = high,
+        secret = secret
+    );
+
+    assert_eq!(transcript, expected);
+    assert_eq!(outcome, Outcome::Won { attempts: 3 });
+}
+
+#[test]
+fn running_out_of_guesses_reveals_the_number() {
+    let difficulty = Difficulty::custom(1, 1000, 2).unwrap();
+    let seed = 7;
+    let secret = secret_for(difficulty, seed);
+    let wrong = if secret == 1 { 2 } else { 1 };
+
+    let script = format!("{}\n{}\n", wrong, wrong);
+    let (outcome, transcript) = run(difficulty, seed, &script);
+
+    assert_eq!(outcome, Outcome::Lost { secret });
+    assert!(transcript.ends_with(&format!("Out of guesses! The number was {}.\n", secret)));
+}
+
+#[test]
+fn unparseable_guesses_do_not_count() {
+    let difficulty = Difficulty::custom(5, 5, 1).unwrap();
+    let (outcome, _) = run(difficulty, 0, "five\n\n5\n");
+
+    assert_eq!(outcome, Outcome::Won { attempts: 1 });
+}
+
+#[test]
+fn parse_guess_explains_what_went_wrong() {
+    let difficulty = Difficulty::Normal;
+
+    assert_eq!(game::parse_guess(" 42\n", difficulty.rules()), Ok(42));
+    assert_eq!(game::parse_guess("\n", difficulty.rules()), Err(GuessError::Empty));
+    assert_eq!(
+        game::parse_guess("forty-two\n", difficulty.rules()),
+        Err(GuessError::NotANumber(String::from("forty-two")))
+    );
+    assert_eq!(
+        game::parse_guess("0\n", difficulty.rules()),
+        Err(GuessError::OutOfRange { guess: 0, low: 1, high: 100 })
+    );
+    assert_eq!(
+        game::parse_guess("-7", difficulty.rules()),
+        Err(GuessError::OutOfRange { guess: -7, low: 1, high: 100 })
+    );
+    assert_eq!(
+        game::parse_guess("5000000000", difficulty.rules()),
+        Err(GuessError::OutOfRange { guess: 5_000_000_000, low: 1, high: 100 })
+    );
+}
+
+#[test]
+fn bad_guesses_are_explained_and_asked_again() {
+    let difficulty = Difficulty::custom(5, 5, 1).unwrap();
+    let (outcome, transcript) = run(difficulty, 0, "five\n\n500\n5\n");
+
+    assert_eq!(outcome, Outcome::Won { attempts: 1 });
+    assert!(transcript.contains("'five' isn't a number. Please type a number.\n"));
+    assert!(transcript.contains("You didn't type anything. Please type a number.\n"));
+    assert!(transcript.contains("500 is out of range. The number is between 5 and 5.\n"));
+}
+
+#[test]
+fn end_of_input_quits_instead_of_spinning() {
+    let difficulty = Difficulty::Normal;
+    let secret = secret_for(difficulty, 3);
+    let (outcome, transcript) = run(difficulty, 3, "");
+
+    assert_eq!(outcome, Outcome::Quit { secret });
+    assert!(transcript.ends_with(&format!(
+        "No more input, so that's the end of the game. The number was {}.\n",
+        secret
+    )));
+}
+
+#[test]
+fn history_narrows_the_possible_range() {
+    let mut game = Game::with_secret(Difficulty::Normal, 40);
+
+    game.guess(50);
+    game.guess(25);
+    game.guess(60);
+
+    assert_eq!(game.possible(), (26, 49));
+    assert_eq!(
+        game.history(),
+        &[(50, Ordering::Greater), (25, Ordering::Less), (60, Ordering::Greater)][..]
+    );
+}
+
+#[test]
+fn wasted_guesses_are_warned_about() {
+    let mut game = Game::with_secret(Difficulty::Normal, 40);
+    game.guess(50);
+    game.guess(25);
+
+    assert_eq!(game.warning(50), Some(Warning::Repeated(50)));
+    assert_eq!(
+        game.warning(10),
+        Some(Warning::AlreadyExcluded { guess: 10, low: 26, high: 49 })
+    );
+    assert_eq!(game.warning(30), None);
+}
+
+#[test]
+fn warnings_show_up_in_the_transcript() {
+    let difficulty = Difficulty::custom(1, 100, 7).unwrap();
+    let seed = 42;
+    let secret = secret_for(difficulty, seed);
+    let script = format!("{}\n{}\n{}\n", secret + 1, secret + 1, secret);
+    let (_, transcript) = run(difficulty, seed, &script);
+
+    assert!(transcript.contains(&format!("You already guessed {}!\n", secret + 1)));
+}
+
+#[test]
+fn temperature_scales_with_the_range() {
+    let normal = Difficulty::Normal.rules();
+    assert_eq!(Temperature::of(51, 50, normal), Temperature::Scorching);
+    assert_eq!(Temperature::of(45, 50, normal), Temperature::Hot);
+    assert_eq!(Temperature::of(60, 50, normal), Temperature::Warm);
+    assert_eq!(Temperature::of(30, 50, normal), Temperature::Cool);
+    assert_eq!(Temperature::of(90, 50, normal), Temperature::Cold);
+    assert_eq!(Temperature::of(1, 100, normal), Temperature::Freezing);
+
+    // Ten away is warm out of a hundred but scorching out of a thousand.
+    assert_eq!(Temperature::of(510, 500, Difficulty::Hard.rules()), Temperature::Scorching);
+}
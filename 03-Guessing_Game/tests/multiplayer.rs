@@ -0,0 +1,125 @@
+use std::io::Cursor;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use guessing_game::difficulty::Difficulty;
+use guessing_game::game::Game;
+use guessing_game::multiplayer::{self, MatchResult};
+
+fn names(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+// The secrets a seeded match will use, one per round.
+
+fn secrets(difficulty: Difficulty, seed: u64, rounds: u32) -> Vec<i64> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..rounds).map(|_| Game::new(difficulty, &mut rng).secret()).collect()
+}
+
+fn run(players: &[&str], rounds: u32, difficulty: Difficulty, seed: u64, script: &str) -> (MatchResult, String) {
+    let mut input = Cursor::new(script.as_bytes().to_vec());
+    let mut output = Vec::new();
+    let result = multiplayer::play_match(
+        &names(players),
+        rounds,
+        difficulty,
+        &mut StdRng::seed_from_u64(seed),
+        &mut input,
+        &mut output,
+    )
+    .unwrap();
+
+    (result, String::from_utf8(output).unwrap())
+}
+
+// A guess that's in range but definitely wrong.
+fn miss(secret: i64) -> i64 {
+    if secret == 1 {
+        2
+    } else {
+        1
+    }
+}
+
+#[test]
+fn players_take_turns_and_the_finder_wins_the_round() {
+    let difficulty = Difficulty::Normal;
+    let secret = secrets(difficulty, 11, 1)[0];
+
+    // ann misses, bob gets it.
+    let script = format!("{}\n{}\n", miss(secret), secret);
+    let (result, transcript) = run(&["ann", "bob"], 1, difficulty, 11, &script);
+
+    assert_eq!(result.players[0].attempts, 1);
+    assert_eq!(result.players[1].attempts, 1);
+    assert_eq!(result.players[1].rounds_won, 1);
+    assert_eq!(result.winner().unwrap().name, "bob");
+    assert!(transcript.contains("bob takes the round!"));
+    assert!(transcript.contains("bob wins the match!"));
+}
+
+#[test]
+fn first_turn_rotates_between_rounds() {
+    let difficulty = Difficulty::Normal;
+    let secrets = secrets(difficulty, 5, 2);
+
+    // Round one starts with ann, round two with bob: ann wins both.
+    let script = format!("{}\n{}\n{}\n", secrets[0], miss(secrets[1]), secrets[1]);
+    let (result, transcript) = run(&["ann", "bob"], 2, difficulty, 5, &script);
+
+    let round_two = transcript.split("Round 2 of 2").nth(1).unwrap();
+    assert!(round_two.find("bob, please").unwrap() < round_two.find("ann, please").unwrap());
+    assert_eq!(result.players[0].rounds_won, 2);
+    assert_eq!(result.rounds_played, 2);
+}
+
+#[test]
+fn best_of_stops_once_the_match_is_decided() {
+    let difficulty = Difficulty::Normal;
+    let secrets = secrets(difficulty, 8, 3);
+
+    // ann wins rounds one and two, so round three never happens.
+    let script = format!("{}\n{}\n{}\n", secrets[0], miss(secrets[1]), secrets[1]);
+    let (result, transcript) = run(&["ann", "bob"], 3, difficulty, 8, &script);
+
+    assert_eq!(result.rounds_played, 2);
+    assert!(result.finished);
+    assert!(!transcript.contains("Round 3"));
+    assert_eq!(result.winner().unwrap().name, "ann");
+}
+
+#[test]
+fn a_round_nobody_wins_reveals_the_number() {
+    let difficulty = Difficulty::custom(1, 100, 1).unwrap();
+    let secret = secrets(difficulty, 2, 1)[0];
+    let script = format!("{}\n{}\n", miss(secret), miss(secret));
+    let (result, transcript) = run(&["ann", "bob"], 1, difficulty, 2, &script);
+
+    assert!(transcript.contains(&format!("Everyone is out of guesses! The number was {}.", secret)));
+    assert!(result.winner().is_none());
+    assert!(transcript.contains("It's a draw."));
+}
+
+#[test]
+fn summary_table_lists_every_player() {
+    let difficulty = Difficulty::Normal;
+    let secret = secrets(difficulty, 3, 1)[0];
+    let script = format!("{}\n", secret);
+    let (result, transcript) = run(&["ann", "bob", "cy"], 1, difficulty, 3, &script);
+
+    assert_eq!(result.players.len(), 3);
+    assert!(transcript.contains("Player           Rounds won  Guesses"));
+    for name in &["ann", "bob", "cy"] {
+        assert!(transcript.lines().any(|line| line.starts_with(name)));
+    }
+}
+
+#[test]
+fn running_out_of_input_ends_the_match_early() {
+    let (result, _) = run(&["ann", "bob"], 3, Difficulty::Normal, 1, "");
+
+    assert!(!result.finished);
+    assert_eq!(result.rounds_played, 0);
+}
@@ -0,0 +1,171 @@
+use std::io::{BufRead, BufReader, Cursor, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::thread;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use guessing_game::difficulty::Difficulty;
+use guessing_game::game::{Game, Outcome};
+use guessing_game::net::{self, ClientMessage, ServerMessage};
+
+// Starts a server on a free localhost port and leaves it running in the
+// background for the rest of the test.
+
+fn start_server(difficulty: Difficulty, seed: u64) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    thread::spawn(move || net::serve(listener, difficulty, StdRng::seed_from_u64(seed)));
+
+    addr
+}
+
+struct Connection {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Connection {
+    fn open(addr: SocketAddr) -> Connection {
+        let stream = TcpStream::connect(addr).unwrap();
+        Connection {
+            reader: BufReader::new(stream.try_clone().unwrap()),
+            writer: stream,
+        }
+    }
+
+    fn receive(&mut self) -> ServerMessage {
+        let mut line = String::new();
+        self.reader.read_line(&mut line).unwrap();
+        line.parse().unwrap()
+    }
+
+    fn send(&mut self, message: ClientMessage) -> ServerMessage {
+        writeln!(self.writer, "{}", message).unwrap();
+        self.receive()
+    }
+
+    fn send_raw(&mut self, line: &str) -> ServerMessage {
+        writeln!(self.writer, "{}", line).unwrap();
+        self.receive()
+    }
+}
+
+// Plays a whole game over the protocol with a binary search.
+
+fn binary_search(connection: &mut Connection) -> i64 {
+    let (mut low, mut high) = match connection.receive() {
+        ServerMessage::Hello { low, high, .. } => (low, high),
+        other => panic!("expected HELLO, got {:?}", other),
+    };
+
+    loop {
+        let guess = low + (high - low) / 2;
+        match connection.send(ClientMessage::Guess(guess.to_string())) {
+            ServerMessage::Low => low = guess + 1,
+            ServerMessage::High => high = guess - 1,
+            ServerMessage::Win(_) => return guess,
+            other => panic!("unexpected reply {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn messages_survive_a_round_trip_through_text() {
+    let messages = vec![
+        ServerMessage::Hello { low: 1, high: 100, guesses: 7 },
+        ServerMessage::Low,
+        ServerMessage::High,
+        ServerMessage::Win(3),
+        ServerMessage::Lose(42),
+        ServerMessage::Bye(42),
+        ServerMessage::Error(String::from("'x' isn't a number. Please type a number.")),
+    ];
+    for message in messages {
+        assert_eq!(message.to_string().parse::<ServerMessage>(), Ok(message));
+    }
+
+    assert_eq!("GUESS 12".parse(), Ok(ClientMessage::Guess(String::from("12"))));
+    assert_eq!("quit".parse(), Ok(ClientMessage::Quit));
+    assert!("SHOUT 12".parse::<ClientMessage>().is_err());
+}
+
+#[test]
+fn session_speaks_the_protocol_in_memory() {
+    let game = Game::with_secret(Difficulty::Normal, 30);
+    let mut reader = Cursor::new(b"GUESS 50\nGUESS 10\nGUESS nope\nDANCE\nGUESS 30\n".to_vec());
+    let mut writer = Vec::new();
+
+    let outcome = net::session(game, &mut reader, &mut writer).unwrap();
+    let replies: Vec<String> = String::from_utf8(writer).unwrap().lines().map(String::from).collect();
+
+    assert_eq!(outcome, Outcome::Won { attempts: 3 });
+    assert_eq!(replies[0], "HELLO 1 100 7");
+    assert_eq!(replies[1], "HIGH");
+    assert_eq!(replies[2], "LOW");
+    assert!(replies[3].starts_with("ERR 'nope' isn't a number"));
+    assert!(replies[4].starts_with("ERR unknown command"));
+    assert_eq!(replies[5], "WIN 3");
+}
+
+#[test]
+fn running_out_of_guesses_loses() {
+    let game = Game::with_secret(Difficulty::custom(1, 100, 2).unwrap(), 30);
+    let mut reader = Cursor::new(b"GUESS 1\nGUESS 2\n".to_vec());
+    let mut writer = Vec::new();
+
+    let outcome = net::session(game, &mut reader, &mut writer).unwrap();
+
+    assert_eq!(outcome, Outcome::Lost { secret: 30 });
+    assert!(String::from_utf8(writer).unwrap().ends_with("LOW\nLOSE 30\n"));
+}
+
+#[test]
+fn many_clients_can_play_at_once_over_localhost() {
+    let addr = start_server(Difficulty::Hard, 1);
+
+    let players: Vec<_> = (0..4)
+        .map(|_| thread::spawn(move || binary_search(&mut Connection::open(addr))))
+        .collect();
+
+    for player in players {
+        let secret = player.join().unwrap();
+        assert!((1..=1000).contains(&secret));
+    }
+}
+
+#[test]
+fn bad_input_and_quit_over_localhost() {
+    let addr = start_server(Difficulty::Normal, 2);
+    let mut connection = Connection::open(addr);
+    connection.receive();
+
+    match connection.send_raw("GUESS 500") {
+        ServerMessage::Error(message) => assert!(message.contains("out of range")),
+        other => panic!("expected an error, got {:?}", other),
+    }
+    match connection.send(ClientMessage::Quit) {
+        ServerMessage::Bye(secret) => assert!((1..=100).contains(&secret)),
+        other => panic!("expected BYE, got {:?}", other),
+    }
+}
+
+#[test]
+fn terminal_client_plays_against_the_server() {
+    let difficulty = Difficulty::Normal;
+    let seed = 9;
+    let secret = Game::new(difficulty, &mut StdRng::seed_from_u64(seed)).secret();
+    let addr = start_server(difficulty, seed);
+
+    let script = format!("{}\n{}\n", if secret == 1 { 2 } else { 1 }, secret);
+    let mut input = Cursor::new(script.into_bytes());
+    let mut output = Vec::new();
+
+    let outcome = net::connect(addr, &mut input, &mut output).unwrap();
+    let transcript = String::from_utf8(output).unwrap();
+
+    assert_eq!(outcome, Outcome::Won { attempts: 2 });
+    assert!(transcript.starts_with("Connected! I'm thinking of a number between 1 and 100."));
+    assert!(transcript.ends_with("Wow, you got it!\n"));
+}
@@ -0,0 +1,114 @@
+use std::cmp::Ordering;
+use std::io::Cursor;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use guessing_game::difficulty::Difficulty;
+use guessing_game::game::{self, Game, GuessError, Outcome, Rules};
+use guessing_game::number::Number;
+use guessing_game::solver::{self, BinarySearch};
+
+fn run<N: Number>(rules: Rules<N>, seed: u64, script: &str) -> (Outcome<N>, String) {
+    let mut input = Cursor::new(script.as_bytes().to_vec());
+    let mut output = Vec::new();
+    let outcome = game::play(rules, &mut StdRng::seed_from_u64(seed), &mut input, &mut output).unwrap();
+
+    (outcome, String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn compare_respects_the_tolerance() {
+    assert_eq!(3i64.compare(5, 0), Ordering::Less);
+    assert_eq!(5i64.compare(5, 0), Ordering::Equal);
+    assert_eq!(7u32.compare(5, 0), Ordering::Greater);
+    assert_eq!((-2i32).compare(-1, 1), Ordering::Equal);
+
+    assert_eq!(3.0f64.compare(3.4, 0.5), Ordering::Equal);
+    assert_eq!(3.0f64.compare(3.6, 0.5), Ordering::Less);
+    assert_eq!(4.2f32.compare(3.6, 0.5), Ordering::Greater);
+}
+
+#[test]
+fn negative_custom_ranges_parse_and_play() {
+    let difficulty: Difficulty = "custom:-500..500:12".parse().unwrap();
+    assert_eq!((difficulty.low(), difficulty.high()), (-500, 500));
+
+    for seed in 0..50 {
+        let secret = Game::new(difficulty, &mut StdRng::seed_from_u64(seed)).secret();
+        assert!((-500..=500).contains(&secret));
+    }
+
+    let secret = Game::new(difficulty, &mut StdRng::seed_from_u64(4)).secret();
+    let script = format!("{}\n{}\n", -500, secret);
+    let (outcome, transcript) = run(difficulty.rules(), 4, &script);
+
+    assert!(transcript.starts_with("I'm thinking of a number between -500 and 500."));
+    assert_eq!(outcome.won_in(), Some(if secret == -500 { 1 } else { 2 }));
+}
+
+#[test]
+fn binary_search_handles_negative_ranges() {
+    let difficulty = Difficulty::custom(-64, 63, 8).unwrap();
+    let bound = solver::binary_search_bound(difficulty);
+    for secret in -64..=63 {
+        assert!(solver::solve(&mut BinarySearch::new(difficulty), secret) <= bound);
+    }
+}
+
+#[test]
+fn float_guesses_within_epsilon_win() {
+    let rules = Rules::from_difficulty(Difficulty::Normal, 0.5).unwrap();
+    let secret = Game::from_rules(rules, &mut StdRng::seed_from_u64(1)).secret();
+
+    let close = secret + 0.4;
+    let far = if secret > 50.0 { secret - 10.0 } else { secret + 10.0 };
+    let script = format!("{}\n{}\n", far, close);
+    let (outcome, transcript) = run(rules, 1, &script);
+
+    assert_eq!(outcome, Outcome::Won { attempts: 2 });
+    assert!(transcript.contains("Anything within 0.5 of it counts."));
+}
+
+#[test]
+fn float_history_narrows_by_the_tolerance() {
+    let rules = Rules::new(0.0, 10.0, 5, 0.25).unwrap();
+    let mut game = Game::from_rules_with_secret(rules, 6.0);
+
+    assert_eq!(game.guess(2.0), Ordering::Less);
+    assert_eq!(game.guess(9.0), Ordering::Greater);
+    assert_eq!(game.possible(), (2.25, 8.75));
+    assert_eq!(game.guess(6.2), Ordering::Equal);
+}
+
+#[test]
+fn floats_reject_nan_and_infinity() {
+    let rules = Rules::new(0.0, 10.0, 5, 0.1).unwrap();
+
+    assert_eq!(game::parse_guess("2.5", rules), Ok(2.5));
+    assert_eq!(
+        game::parse_guess("NaN", rules),
+        Err(GuessError::NotANumber(String::from("NaN")))
+    );
+    assert_eq!(
+        game::parse_guess("inf", rules),
+        Err(GuessError::NotANumber(String::from("inf")))
+    );
+    assert_eq!(
+        game::parse_guess("10.5", rules),
+        Err(GuessError::OutOfRange { guess: 10.5, low: 0.0, high: 10.0 })
+    );
+}
+
+#[test]
+fn rules_are_validated() {
+    assert!(Rules::new(5, 1, 3, 0).is_err());
+    assert!(Rules::new(1, 5, 0, 0).is_err());
+    assert!(Rules::new(0.0, 1.0, 3, -0.1).is_err());
+    assert!(Rules::new(0.0, f64::INFINITY, 3, 0.1).is_err());
+
+    // A negative range doesn't fit in an unsigned number.
+    let difficulty = Difficulty::custom(-5, 5, 3).unwrap();
+    assert!(Rules::<u32>::from_difficulty(difficulty, 0).is_err());
+    assert!(Rules::<i32>::from_difficulty(difficulty, 0).is_ok());
+}
@@ -0,0 +1,82 @@
+use std::cmp::Ordering;
+use std::io::Cursor;
+
+use guessing_game::difficulty::Difficulty;
+use guessing_game::reverse::{self, ReverseOutcome};
+
+fn run(script: &str) -> (ReverseOutcome, String) {
+    let mut input = Cursor::new(script.as_bytes().to_vec());
+    let mut output = Vec::new();
+    let outcome = reverse::play(Difficulty::Normal, &mut input, &mut output).unwrap();
+
+    (outcome, String::from_utf8(output).unwrap())
+}
+
+// Answers every question honestly for `secret`, by reading the guesses back
+// out of the transcript as the game goes.
+
+fn honest_script(secret: u32) -> String {
+    let mut script = String::new();
+
+    loop {
+        let (_, transcript) = run(&script);
+        let guess: u32 = transcript
+            .lines()
+            .filter_map(|line| line.strip_prefix("Is it "))
+            .next_back()
+            .and_then(|rest| rest.split('?').next())
+            .and_then(|n| n.parse().ok())
+            .unwrap();
+
+        match guess.cmp(&secret) {
+            Ordering::Less => script.push_str("higher\n"),
+            Ordering::Greater => script.push_str("lower\n"),
+            Ordering::Equal => {
+                script.push_str("correct\n");
+                return script;
+            }
+        }
+    }
+}
+
+#[test]
+fn honest_answers_get_the_number_in_seven_guesses_or_fewer() {
+    for secret in 1..=100 {
+        let (outcome, _) = run(&honest_script(secret));
+        match outcome {
+            ReverseOutcome::Guessed { guesses } => assert!(guesses <= 7),
+            other => panic!("secret {} ended with {:?}", secret, other),
+        }
+    }
+}
+
+#[test]
+fn nonsense_answers_are_asked_again() {
+    let (outcome, transcript) = run("maybe\n\nc\n");
+
+    assert_eq!(outcome, ReverseOutcome::Guessed { guesses: 1 });
+    assert_eq!(transcript.matches("Please answer higher, lower or correct.").count(), 2);
+    assert_eq!(transcript.matches("Is it 50?").count(), 3);
+}
+
+#[test]
+fn contradictory_answers_are_caught() {
+    let (outcome, transcript) = run("higher\nlower\nhigher\nhigher\nhigher\nhigher\nhigher\n");
+
+    assert_eq!(outcome, ReverseOutcome::Cheated);
+    assert!(transcript.contains("Cheater!"));
+}
+
+#[test]
+fn claiming_a_number_outside_the_range_is_caught() {
+    let (outcome, transcript) = run("l\nl\nl\nl\nl\nl\nl\n");
+
+    assert_eq!(outcome, ReverseOutcome::Cheated);
+    assert!(transcript.contains("lower than 1, but it can't be less than 1"));
+}
+
+#[test]
+fn running_out_of_input_quits() {
+    let (outcome, _) = run("higher\n");
+    assert_eq!(outcome, ReverseOutcome::Quit);
+}
@@ -0,0 +1,89 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+
+use guessing_game::difficulty::Difficulty;
+use guessing_game::scores::{Score, ScoreBoard, ScoreError};
+
+// A fresh path under the system temp dir for each test.
+
+fn temp_path(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("guessing_game_scores_{}_{}", process::id(), name));
+    let _ = fs::remove_dir_all(&dir);
+    dir.join("scores.json")
+}
+
+#[test]
+fn missing_file_is_an_empty_board() {
+    let board = ScoreBoard::load(&temp_path("missing")).unwrap();
+    assert!(board.is_empty());
+}
+
+#[test]
+fn scores_survive_a_round_trip() {
+    let path = temp_path("round_trip");
+
+    let mut board = ScoreBoard::default();
+    board.record(Score::new("ferris", 4, Difficulty::Normal));
+    board.record(Score::new("corro", 9, Difficulty::Hard));
+    board.save(&path).unwrap();
+
+    let loaded = ScoreBoard::load(&path).unwrap();
+    assert_eq!(loaded.ranked(), board.ranked());
+}
+
+#[test]
+fn corrupt_file_is_reported() {
+    let path = temp_path("corrupt");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, "{ this is not json").unwrap();
+
+    match ScoreBoard::load(&path) {
+        Err(ScoreError::Corrupt(_)) => {}
+        other => panic!("expected a corrupt-file error, got {:?}", other),
+    }
+}
+
+#[test]
+fn fewest_attempts_rank_first_and_bigger_ranges_break_ties() {
+    let mut board = ScoreBoard::default();
+    board.record(Score::new("slow", 7, Difficulty::Normal));
+    board.record(Score::new("easy", 3, Difficulty::Easy));
+    board.record(Score::new("hard", 3, Difficulty::Hard));
+
+    let names: Vec<&str> = board.ranked().iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["hard", "easy", "slow"]);
+}
+
+#[test]
+fn ranges_wider_than_an_i64_still_rank() {
+    let widest = Difficulty::custom(i64::MIN, i64::MAX - 1, 64).unwrap();
+    let mut board = ScoreBoard::default();
+    board.record(Score::new("first", 5, widest));
+    board.record(Score::new("second", 5, widest));
+    board.record(Score::new("hard", 5, Difficulty::Hard));
+
+    // A hand-edited file can have the bounds the wrong way round.
+    let mut backwards = Score::new("backwards", 5, Difficulty::Normal);
+    backwards.low = i64::MAX;
+    backwards.high = i64::MIN;
+    board.record(backwards);
+
+    let names: Vec<&str> = board.ranked().iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["first", "second", "hard", "backwards"]);
+}
+
+#[test]
+fn leaderboard_lists_every_score() {
+    let mut board = ScoreBoard::default();
+    board.record(Score::new("ferris", 4, Difficulty::Normal));
+
+    let mut output = Vec::new();
+    board.print_leaderboard(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.starts_with("Rank"));
+    assert!(output.contains("ferris"));
+    assert!(output.contains("1..100"));
+}
@@ -0,0 +1,62 @@
+use std::io;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use guessing_game::difficulty::Difficulty;
+use guessing_game::game::{Game, Outcome};
+use guessing_game::solver::{self, BinarySearch, RandomSearch};
+
+#[test]
+fn binary_search_stays_within_its_bound_for_every_secret() {
+    for &difficulty in &[Difficulty::Easy, Difficulty::Normal, Difficulty::Hard] {
+        let bound = solver::binary_search_bound(difficulty);
+
+        for secret in difficulty.low()..=difficulty.high() {
+            let guesses = solver::solve(&mut BinarySearch::new(difficulty), secret);
+            assert!(guesses <= bound, "{} took {} guesses", secret, guesses);
+        }
+    }
+}
+
+#[test]
+fn binary_search_bound_matches_log2() {
+    assert_eq!(solver::binary_search_bound(Difficulty::Normal), 7);
+    assert_eq!(solver::binary_search_bound(Difficulty::Hard), 10);
+    assert_eq!(solver::binary_search_bound(Difficulty::custom(4, 4, 1).unwrap()), 1);
+}
+
+#[test]
+fn random_search_always_finds_the_number() {
+    let difficulty = Difficulty::custom(1, 20, 20).unwrap();
+
+    for secret in 1..=20 {
+        let mut strategy = RandomSearch::new(difficulty, StdRng::seed_from_u64(secret as u64));
+        let guesses = solver::solve(&mut strategy, secret);
+        assert!(guesses <= 20);
+    }
+}
+
+#[test]
+fn binary_search_wins_every_normal_game() {
+    let difficulty = Difficulty::Normal;
+
+    for secret in 1..=100 {
+        let mut game = Game::with_secret(difficulty, secret);
+        let outcome = solver::play_auto(&mut BinarySearch::new(difficulty), &mut game, &mut io::sink()).unwrap();
+        assert!(matches!(outcome, Outcome::Won { .. }));
+    }
+}
+
+#[test]
+fn benchmark_reports_average_and_worst_case() {
+    let difficulty = Difficulty::Normal;
+    let mut rng = StdRng::seed_from_u64(1);
+
+    let report = solver::benchmark(difficulty, 500, &mut rng, |_| Box::new(BinarySearch::new(difficulty)));
+
+    assert_eq!(report.strategy, "binary search");
+    assert_eq!(report.games, 500);
+    assert!(report.worst <= 7);
+    assert!(report.average >= 1.0 && report.average <= f64::from(report.worst));
+}
@@ -0,0 +1,125 @@
+use std::io::{self, Write};
+
+use crate::geometry::Rectanglo;
+use crate::render;
+use crate::shapes::Shape;
+
+// The rectangle calculator. Rectangles are given as WIDTHxHEIGHT (`30x50`),
+// or as a width and a height (`30 50`) where that isn't ambiguous.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Area(Rectanglo),
+    Border(Rectanglo),
+    CanHold(Rectanglo, Rectanglo),
+    Square(u32),
+    Draw(Rectanglo),
+    Repl,
+    Tour,
+}
+
+pub const USAGE: &str = "\
+Usage: structs <COMMAND>
+
+Commands:
+  area <W>x<H>                  area of a rectangle (or: area <W> <H>)
+  border <W>x<H>                length of its border (or: border <W> <H>)
+  can-hold <W>x<H> <W>x<H>      whether the first rectangle can hold the second
+  square <SIZE>                 a square's area and border
+  draw <W>x<H>                  draw a rectangle to fit the terminal
+  repl                          work with named rectangles interactively
+  tour                          walk through the chapter's examples
+  help                          print this help";
+
+impl Command {
+    // Parses the arguments that follow the program name.
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Command, String> {
+        let mut args = args.into_iter();
+        let name = args.next().ok_or_else(|| String::from(USAGE))?;
+
+        let command = match name.as_str() {
+            "area" => Command::Area(rectangle(&mut args, &name)?),
+            "border" => Command::Border(rectangle(&mut args, &name)?),
+            "can-hold" => {
+                let outer = rectangle(&mut args, &name)?;
+                Command::CanHold(outer, rectangle(&mut args, &name)?)
+            }
+            "square" => {
+                let size = args
+                    .next()
+                    .ok_or_else(|| String::from("square needs a size, e.g. square 3"))?;
+                Command::Square(parse_length(&size, "size")?)
+            }
+            "draw" => Command::Draw(rectangle(&mut args, &name)?),
+            "repl" => Command::Repl,
+            "tour" => Command::Tour,
+            "help" | "-h" | "--help" => return Err(String::from(USAGE)),
+            other => return Err(format!("unknown command '{}'\n\n{}", other, USAGE)),
+        };
+
+        match args.next() {
+            Some(extra) => Err(format!("unexpected argument '{}' after {}", extra, name)),
+            None => Ok(command),
+        }
+    }
+}
+
+// `30x50`, as a rectangle at the origin.
+
+pub fn parse_size(text: &str) -> Result<Rectanglo, String> {
+    let (width, height) = text
+        .split_once(['x', 'X'])
+        .ok_or_else(|| format!("'{}' isn't a size: expected WIDTHxHEIGHT, e.g. 30x50", text))?;
+
+    Ok(Rectanglo::sized(parse_length(width, "width")?, parse_length(height, "height")?))
+}
+
+// One side of a rectangle. `what` names it in the error message.
+pub fn parse_length(text: &str, what: &str) -> Result<u32, String> {
+    text.trim().parse().map_err(|_| {
+        format!(
+            "'{}' isn't a valid {} (expected a whole number from 0 to {})",
+            text,
+            what,
+            u32::MAX
+        )
+    })
+}
+
+// Takes either `WxH`, or a width followed by a height.
+fn rectangle<I: Iterator<Item = String>>(args: &mut I, command: &str) -> Result<Rectanglo, String> {
+    let first = args
+        .next()
+        .ok_or_else(|| format!("{} needs a rectangle, e.g. {} 30x50", command, command))?;
+
+    if first.contains(['x', 'X']) {
+        return parse_size(&first);
+    }
+
+    let width = parse_length(&first, "width")?;
+    let height = args
+        .next()
+        .ok_or_else(|| format!("{} needs a height after the width {}", command, first))?;
+    Ok(Rectanglo::sized(width, parse_length(&height, "height")?))
+}
+
+// Runs everything but `repl` and `tour`, which need more than an output.
+
+pub fn run<W: Write>(command: &Command, output: &mut W) -> io::Result<()> {
+    match command {
+        Command::Area(rect) => match rect.checked_area() {
+            Some(area) => writeln!(output, "The area of the rectangle is {} square pixels.", area),
+            None => writeln!(output, "{} is too big to measure: its area doesn't fit in a u32.", rect),
+        },
+        Command::Border(rect) => match rect.checked_border() {
+            Some(border) => writeln!(output, "The border length of the rectangle is {} pixels.", border),
+            None => writeln!(output, "{} is too big to measure: its border doesn't fit in a u32.", rect),
+        },
+        Command::CanHold(outer, inner) => {
+            writeln!(output, "Can {} hold {}? {}", outer, inner, outer.can_hold(inner))
+        }
+        Command::Square(size) => render::print_table(&[Rectanglo::square(*size)], output),
+        Command::Draw(rect) => render::draw(rect, render::terminal_width(), output),
+        Command::Repl | Command::Tour => Ok(()),
+    }
+}
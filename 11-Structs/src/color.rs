@@ -0,0 +1,275 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+// The chapter's `Color(i32, i32, i32)` tuple struct, made useful. Channels
+// are `u8`, so a `Color` can't hold a nonsense value like -5 or 300;
+// `Color::new` checks numbers coming in from elsewhere.
+//
+// Colors parse from `#rgb` or `#rrggbb`, `rgb(r, g, b)` or a CSS color name,
+// and display as `#rrggbb`.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColorError {
+    OutOfRange(i64),
+    Syntax(String),
+    UnknownName(String),
+}
+
+impl fmt::Display for ColorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ColorError::OutOfRange(n) => write!(f, "{} is out of range (channels go from 0 to 255)", n),
+            ColorError::Syntax(s) => write!(
+                f,
+                "'{}' isn't a color (try #rrggbb, rgb(r, g, b) or a color name)",
+                s
+            ),
+            ColorError::UnknownName(s) => write!(f, "there's no color called '{}'", s),
+        }
+    }
+}
+
+impl Error for ColorError {}
+
+// Hue in degrees (0 up to 360), saturation and lightness (or value) from 0
+// to 1.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsl {
+    pub h: f64,
+    pub s: f64,
+    pub l: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsv {
+    pub h: f64,
+    pub s: f64,
+    pub v: f64,
+}
+
+// The CSS level 1 colors, plus a few favourites.
+const NAMES: [(&str, Color); 21] = [
+    ("black", Color(0, 0, 0)),
+    ("silver", Color(192, 192, 192)),
+    ("gray", Color(128, 128, 128)),
+    ("grey", Color(128, 128, 128)),
+    ("white", Color(255, 255, 255)),
+    ("maroon", Color(128, 0, 0)),
+    ("red", Color(255, 0, 0)),
+    ("purple", Color(128, 0, 128)),
+    ("fuchsia", Color(255, 0, 255)),
+    ("magenta", Color(255, 0, 255)),
+    ("green", Color(0, 128, 0)),
+    ("lime", Color(0, 255, 0)),
+    ("olive", Color(128, 128, 0)),
+    ("yellow", Color(255, 255, 0)),
+    ("navy", Color(0, 0, 128)),
+    ("blue", Color(0, 0, 255)),
+    ("teal", Color(0, 128, 128)),
+    ("aqua", Color(0, 255, 255)),
+    ("cyan", Color(0, 255, 255)),
+    ("orange", Color(255, 165, 0)),
+    ("rebeccapurple", Color(102, 51, 153)),
+];
+
+impl Color {
+    pub const BLACK: Color = Color(0, 0, 0);
+    pub const WHITE: Color = Color(255, 255, 255);
+
+    pub fn new(r: i64, g: i64, b: i64) -> Result<Color, ColorError> {
+        Ok(Color(channel(r)?, channel(g)?, channel(b)?))
+    }
+
+    pub fn named(name: &str) -> Option<Color> {
+        let name = name.trim().to_lowercase();
+        NAMES.iter().find(|(n, _)| *n == name).map(|&(_, color)| color)
+    }
+
+    // Hex with an optional alpha channel, as editor themes use: `#rgb`,
+    // `#rgba`, `#rrggbb` or `#rrggbbaa`. No alpha means fully opaque (255).
+    pub fn from_hex_alpha(s: &str) -> Result<(Color, u8), ColorError> {
+        let syntax = || ColorError::Syntax(s.to_string());
+        let digits = s.trim().strip_prefix('#').ok_or_else(syntax)?;
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(syntax());
+        }
+
+        let channels: Vec<u8> = match digits.len() {
+            // Each digit is doubled, so #f80 is #ff8800.
+            3 | 4 => digits
+                .chars()
+                .map(|c| c.to_digit(16).unwrap() as u8 * 17)
+                .collect(),
+            6 | 8 => (0..digits.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).unwrap())
+                .collect(),
+            _ => return Err(syntax()),
+        };
+
+        let alpha = channels.get(3).copied().unwrap_or(255);
+        Ok((Color(channels[0], channels[1], channels[2]), alpha))
+    }
+
+    pub fn to_hsl(self) -> Hsl {
+        let (h, max, min) = self.hue();
+        let l = (max + min) / 2.0;
+        let s = if max == min {
+            0.0
+        } else {
+            (max - min) / (1.0 - (2.0 * l - 1.0).abs())
+        };
+
+        Hsl { h, s, l }
+    }
+
+    pub fn from_hsl(hsl: Hsl) -> Color {
+        let l = hsl.l.clamp(0.0, 1.0);
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * hsl.s.clamp(0.0, 1.0);
+        Color::from_hue(hsl.h, chroma, l - chroma / 2.0)
+    }
+
+    pub fn to_hsv(self) -> Hsv {
+        let (h, max, min) = self.hue();
+        let s = if max == 0.0 { 0.0 } else { (max - min) / max };
+
+        Hsv { h, s, v: max }
+    }
+
+    pub fn from_hsv(hsv: Hsv) -> Color {
+        let v = hsv.v.clamp(0.0, 1.0);
+        let chroma = v * hsv.s.clamp(0.0, 1.0);
+        Color::from_hue(hsv.h, chroma, v - chroma)
+    }
+
+    // Interpolates between two colors: 0 gives `self`, 1 gives `other`, and
+    // anything outside that is clamped.
+    pub fn mix(self, other: Color, t: f64) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let lerp = |a: u8, b: u8| (f64::from(a) + (f64::from(b) - f64::from(a)) * t).round() as u8;
+
+        Color(lerp(self.0, other.0), lerp(self.1, other.1), lerp(self.2, other.2))
+    }
+
+    // What this color looks like painted over `background` with the given
+    // alpha (0 to 255), e.g. a `#rrggbbaa` theme color over the editor.
+    pub fn blend_over(self, background: Color, alpha: u8) -> Color {
+        background.mix(self, f64::from(alpha) / 255.0)
+    }
+
+    // WCAG 2 relative luminance: 0 for black, 1 for white.
+    pub fn luminance(self) -> f64 {
+        let linear = |c: u8| {
+            let c = f64::from(c) / 255.0;
+            if c <= 0.03928 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+
+        0.2126 * linear(self.0) + 0.7152 * linear(self.1) + 0.0722 * linear(self.2)
+    }
+
+    // WCAG 2 contrast ratio, from 1 (none) to 21 (black on white). Normal
+    // text wants at least 4.5, large text at least 3.
+    pub fn contrast_ratio(self, other: Color) -> f64 {
+        let (a, b) = (self.luminance(), other.luminance());
+        let (light, dark) = if a > b { (a, b) } else { (b, a) };
+
+        (light + 0.05) / (dark + 0.05)
+    }
+
+    // The hue in degrees, and the biggest and smallest channels from 0 to 1.
+    fn hue(self) -> (f64, f64, f64) {
+        let (r, g, b) = (
+            f64::from(self.0) / 255.0,
+            f64::from(self.1) / 255.0,
+            f64::from(self.2) / 255.0,
+        );
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let chroma = max - min;
+
+        let h = if chroma == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / chroma).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / chroma + 2.0)
+        } else {
+            60.0 * ((r - g) / chroma + 4.0)
+        };
+
+        (h, max, min)
+    }
+
+    // Shared by HSL and HSV: a hue, how colorful it is, and how much grey to
+    // add to every channel.
+    fn from_hue(h: f64, chroma: f64, grey: f64) -> Color {
+        let h = h.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let to_u8 = |c: f64| ((c + grey) * 255.0).round().clamp(0.0, 255.0) as u8;
+
+        Color(to_u8(r), to_u8(g), to_u8(b))
+    }
+}
+
+fn channel(n: i64) -> Result<u8, ColorError> {
+    u8::try_from(n).map_err(|_| ColorError::OutOfRange(n))
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+impl FromStr for Color {
+    type Err = ColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim();
+        let syntax = || ColorError::Syntax(s.to_string());
+
+        if text.starts_with('#') {
+            return match text.len() {
+                4 | 7 => Color::from_hex_alpha(text).map(|(color, _)| color),
+                _ => Err(syntax()),
+            };
+        }
+
+        let lower = text.to_lowercase();
+        if let Some(inside) = lower.strip_prefix("rgb(").and_then(|rest| rest.strip_suffix(')')) {
+            let parts: Vec<&str> = inside.split(',').map(str::trim).collect();
+            if parts.len() != 3 {
+                return Err(syntax());
+            }
+            let mut channels = [0; 3];
+            for (channel, part) in channels.iter_mut().zip(&parts) {
+                *channel = part.parse::<i64>().map_err(|_| syntax())?;
+            }
+            return Color::new(channels[0], channels[1], channels[2]);
+        }
+
+        if lower.chars().all(|c| c.is_ascii_alphabetic()) && !lower.is_empty() {
+            return Color::named(&lower).ok_or_else(|| ColorError::UnknownName(text.to_string()));
+        }
+
+        Err(syntax())
+    }
+}
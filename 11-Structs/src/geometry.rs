@@ -0,0 +1,261 @@
+use std::fmt::{self, Debug, Display};
+
+// The chapter builds the same rectangle twice: `Rectangle`, measured by a
+// free `area3` function, and `Rectanglo`, which grows methods. Both are just a
+// width and a height, so here they become one generic `Rect` that also knows
+// where it sits, via an origin `Point` at its top-left corner.
+
+// Anything a rectangle can be measured in. The arithmetic is all checked, so
+// a rectangle too big for its number type gives `None` instead of
+// overflowing (or, for floats, instead of quietly turning into infinity).
+
+pub trait Num: Copy + PartialOrd + Debug + Display {
+    fn zero() -> Self;
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! integer_num {
+    ($($t:ty),*) => {$(
+        impl Num for $t {
+            fn zero() -> Self {
+                0
+            }
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                <$t>::checked_sub(self, other)
+            }
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+        }
+    )*};
+}
+
+macro_rules! float_num {
+    ($($t:ty),*) => {$(
+        impl Num for $t {
+            fn zero() -> Self {
+                0.0
+            }
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                finite(self + other)
+            }
+
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                finite(self - other)
+            }
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                finite(self * other)
+            }
+        }
+
+        impl Finite for $t {
+            fn is_finite(self) -> bool {
+                <$t>::is_finite(self)
+            }
+        }
+    )*};
+}
+
+trait Finite: Copy {
+    fn is_finite(self) -> bool;
+}
+
+fn finite<T: Finite>(value: T) -> Option<T> {
+    if value.is_finite() {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+integer_num!(u8, u16, u32, u64, i8, i16, i32, i64);
+float_num!(f32, f64);
+
+fn min<T: Num>(a: T, b: T) -> T {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+fn max<T: Num>(a: T, b: T) -> T {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Num> Point<T> {
+    pub fn new(x: T, y: T) -> Point<T> {
+        Point { x, y }
+    }
+
+    pub fn origin() -> Point<T> {
+        Point::new(T::zero(), T::zero())
+    }
+}
+
+// A rectangle covers the points from its origin up to, but not including,
+// `origin + size`, so two rectangles that only share an edge don't overlap.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rect<T> {
+    pub origin: Point<T>,
+    pub width: T,
+    pub height: T,
+}
+
+// The chapter's two rectangles, kept by name. They only ever had a size, so
+// they sit at the origin.
+
+pub type Rectangle = Rect<u32>;
+pub type Rectanglo = Rect<u32>;
+
+impl<T: Num> Rect<T> {
+    // Negative sizes are clamped to zero, so every `Rect` is well formed.
+    pub fn new(origin: Point<T>, width: T, height: T) -> Rect<T> {
+        Rect {
+            origin,
+            width: max(width, T::zero()),
+            height: max(height, T::zero()),
+        }
+    }
+
+    pub fn sized(width: T, height: T) -> Rect<T> {
+        Rect::new(Point::origin(), width, height)
+    }
+
+    // Associated Functions:
+    pub fn square(size: T) -> Rect<T> {
+        Rect::sized(size, size)
+    }
+
+    // `checked_`, like `u32::checked_mul`: `None` if the answer doesn't fit
+    // in a `T`. The plain `area` is the `Shape` one, as an `f64`.
+    pub fn checked_area(&self) -> Option<T> {
+        self.width.checked_mul(self.height)
+    }
+
+    pub fn checked_border(&self) -> Option<T> {
+        let half = self.width.checked_add(self.height)?;
+        half.checked_add(half)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width == T::zero() || self.height == T::zero()
+    }
+
+    // The far edges. `None` if the rectangle runs off the end of `T`.
+    pub fn right(&self) -> Option<T> {
+        self.origin.x.checked_add(self.width)
+    }
+
+    pub fn bottom(&self) -> Option<T> {
+        self.origin.y.checked_add(self.height)
+    }
+
+    pub fn contains_point(&self, point: Point<T>) -> bool {
+        let (right, bottom) = match (self.right(), self.bottom()) {
+            (Some(right), Some(bottom)) => (right, bottom),
+            _ => return false,
+        };
+
+        point.x >= self.origin.x && point.x < right && point.y >= self.origin.y && point.y < bottom
+    }
+
+    // Whether `other` lies entirely within this rectangle, edges included.
+    pub fn contains(&self, other: &Rect<T>) -> bool {
+        match (self.right(), self.bottom(), other.right(), other.bottom()) {
+            (Some(right), Some(bottom), Some(other_right), Some(other_bottom)) => {
+                other.origin.x >= self.origin.x
+                    && other.origin.y >= self.origin.y
+                    && other_right <= right
+                    && other_bottom <= bottom
+            }
+            _ => false,
+        }
+    }
+
+    // The overlap of two rectangles, or `None` if they don't overlap.
+    pub fn intersection(&self, other: &Rect<T>) -> Option<Rect<T>> {
+        let left = max(self.origin.x, other.origin.x);
+        let top = max(self.origin.y, other.origin.y);
+        let right = min(self.right()?, other.right()?);
+        let bottom = min(self.bottom()?, other.bottom()?);
+
+        if left >= right || top >= bottom {
+            return None;
+        }
+
+        Some(Rect::new(
+            Point::new(left, top),
+            right.checked_sub(left)?,
+            bottom.checked_sub(top)?,
+        ))
+    }
+
+    // The smallest rectangle covering both, i.e. their bounding box.
+    pub fn union(&self, other: &Rect<T>) -> Option<Rect<T>> {
+        let left = min(self.origin.x, other.origin.x);
+        let top = min(self.origin.y, other.origin.y);
+        let right = max(self.right()?, other.right()?);
+        let bottom = max(self.bottom()?, other.bottom()?);
+
+        Some(Rect::new(
+            Point::new(left, top),
+            right.checked_sub(left)?,
+            bottom.checked_sub(top)?,
+        ))
+    }
+
+    pub fn translate(&self, dx: T, dy: T) -> Option<Rect<T>> {
+        let origin = Point::new(self.origin.x.checked_add(dx)?, self.origin.y.checked_add(dy)?);
+        Some(Rect { origin, ..*self })
+    }
+
+    // Scales the size, keeping the origin where it is.
+    pub fn scale(&self, factor: T) -> Option<Rect<T>> {
+        Some(Rect::new(
+            self.origin,
+            self.width.checked_mul(factor)?,
+            self.height.checked_mul(factor)?,
+        ))
+    }
+}
+
+impl<T: Num> fmt::Display for Point<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+// `30x50`, or `30x50 at (10, 20)` when it isn't at the origin. This is the
+// same WIDTHxHEIGHT that the command line reads.
+
+impl<T: Num> fmt::Display for Rect<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}x{}", self.width, self.height)?;
+        if self.origin != Point::origin() {
+            write!(f, " at {}", self.origin)?;
+        }
+        Ok(())
+    }
+}
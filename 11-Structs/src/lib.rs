@@ -0,0 +1,14 @@
+// Chapter 5, using structs to structure related data. The structs live in this
+// library so they can be tested; main.rs walks through them.
+
+pub mod cli;
+pub mod color;
+pub mod geometry;
+pub mod packing;
+pub mod registry;
+pub mod render;
+pub mod repl;
+pub mod shapes;
+pub mod store;
+pub mod user;
+pub mod vector;
@@ -1,65 +1,31 @@
+use std::io;
+use std::process;
 
-// A basic struct:
+use structs::cli::{self, Command};
+use structs::geometry::{Rectangle, Rectanglo};
+use structs::{render, repl};
+use structs::shapes::Shape;
+use structs::vector::Vec3;
 
-struct User {
-    username: String,
-    email: String,
-    sign_in_count: u64,
-    active: bool,
-}
-
-// Returning a struct from a function:
-
-fn build_user(email: String, username: String) -> User {
-
-    // Because the parameter names and the struct field names are exactly 
-    // the same in, we can use the field init shorthand syntax
-
-    User {
-        email,
-        username,
-        active: true,
-        sign_in_count: 1,
-    }
-
-    // Or, to conSTRUCT in a static fashion:
-
-    // let user1 = User {
-    //     email: String::from("someone@example.com"),
-    //     username: String::from("someusername123"),
-    //     active: true,
-    //     sign_in_count: 1,
-    // };
-
-
-    // Using struct update syntax, we can achieve the same effect with less code:
-
-    // let user2 = User {
-    //     email: String::from("another@example.com"),
-    //     username: String::from("anotherusername567"),
-    //     ..user1        // remaining values are set from the previous instance of User
-    // };
-
-}
+// The struct definitions from the start of the chapter live in the library:
+// `User` in src/user.rs, and the rectangles in src/geometry.rs.
 
 
 // You can also define structs that look similar to tuples, called tuple structs. 
 // Tuple structs have the added meaning the struct name provides but don’t have names
 // associated with their fields; rather, they just have the types of the fields.
 
-
-struct Color(i32, i32, i32);
-struct Point(i32, i32, i32);
+// struct Color(i32, i32, i32);     now a real color type in src/color.rs
+// struct Point(i32, i32, i32);     now the 3D vector `Vec3` in src/vector.rs
 
 // let black = Color(0, 0, 0);
-// let origin = Point(0, 0, 0);
+// let origin = Vec3(0, 0, 0);
 
 
 // Each struct you define is its own type, even though the fields 
 // within the struct have the same types.
 
 
-
 // It’s possible for structs to store references to data owned by something else, 
 // but to do so requires the use of lifetimes. Lifetimes ensure that the data 
 // referenced by a struct is valid for as long as the struct is. See chapter 10.
@@ -69,6 +35,32 @@ struct Point(i32, i32, i32);
 
 
 fn main() {
+    let command = match Command::parse(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("{}", message);
+            process::exit(2);
+        }
+    };
+
+    let result = match command {
+        Command::Tour => {
+            tour();
+            Ok(())
+        }
+        Command::Repl => repl::repl(&mut io::stdin().lock(), &mut io::stdout()),
+        command => cli::run(&command, &mut io::stdout()),
+    };
+
+    if let Err(e) = result {
+        eprintln!("structs: {}", e);
+        process::exit(1);
+    }
+}
+
+// The chapter's examples, one after another.
+
+fn tour() {
     let width1 = 30;
     let height1 = 50;
 
@@ -81,6 +73,7 @@ fn main() {
     main3();
     main4();
     main5();
+    main6();
 }
 
 fn area(width: u32, height: u32) -> u32 {
@@ -108,40 +101,38 @@ fn area2(dimensions: (u32, u32)) -> u32 {
 
 
 
-#[derive(Debug)]    // structs don’t have a provided implementation of Display, 
-                    // becauase ambiguity of types. ergo this is necessary.
-
-struct Rectangle {
-    width: u32,
-    height: u32,
-}
+// `Rectangle` is `geometry::Rect<u32>`, which has `#[derive(Debug)]` on it in
+// src/geometry.rs. structs don’t have a provided implementation of Display,
+// becauase ambiguity of types, so deriving Debug is what lets us print one
+// with {:?}. (`Rect` does now have a hand-written Display as well, next to
+// it in src/geometry.rs, that prints `30x50`.)
 
 fn main3() {
-    let rect1 = Rectangle {
-        width: 30,
-        height: 50,
-    };
+    let rect1 = Rectangle::sized(30, 50);
 
-    println!(
-        "The area of the rectangle is {} square pixels.",
-        area3(&rect1)
-    );
+    match area3(&rect1) {
+        Some(area) => println!("The area of the rectangle is {} square pixels.", area),
+        None => println!("The rectangle is too big to measure."),
+    }
 
-    printRect(&rect1);
+    print_rect(&rect1);
 }
 
-fn area3(rectangle: &Rectangle) -> u32 {
-    rectangle.width * rectangle.height
+fn area3(rectangle: &Rectangle) -> Option<u32> {
+    rectangle.checked_area()
 }
 
 
 // Adding Useful Functionality with Derived Traits:
 
-fn printRect(rectangle: &Rectangle) {
+fn print_rect(rectangle: &Rectangle) {
     println!("rect1 is {:?}", rectangle);
     
     println!("rect1 is {:#?}", rectangle);
 
+    println!("rect1 is {}", rectangle);
+
+    render::draw(rectangle, render::terminal_width(), &mut io::stdout()).expect("couldn't draw to stdout");
 }
 
 
@@ -152,72 +143,38 @@ fn printRect(rectangle: &Rectangle) {
 // parameter and instead make an area 
 // method defined on the Rectangle struct
 
-#[derive(Debug)]
-
-struct Rectanglo {
-    width: u32,
-    height: u32,
-}
-
-impl Rectanglo {
-    fn area(&self) -> u32 {
-        self.width * self.height
-    }
+// `Rectanglo` is the same `geometry::Rect<u32>`, and its methods
+// (`checked_area`, `checked_border` and the `square` associated function)
+// are defined on `Rect` in src/geometry.rs. Because the sums could overflow,
+// they return an `Option`. `can_hold` comes from the `Shape` trait in
+// src/shapes.rs, so a rectangle can also be asked about circles and
+// triangles.
 
-    fn border(&self) -> u32 {
-        (self.width + self.height) * 2
-    }
-
-    // Methods with More Parameters:
-    fn can_hold(&self, other: &Rectanglo) -> bool {
-        self.width > other.width && self.height > other.height
-    }
-
-    // Associated Functions:
-    fn square(size: u32) -> Rectanglo {
-        Rectanglo {
-            width: size,
-            height: size,
-        }
-    }
-    // To call this associated function, we use the :: syntax with the struct name; let sq = Rectangle::square(3); is an example.
-}
+// To call an associated function, we use the :: syntax with the struct
+// name; let sq = Rectanglo::square(3); is an example.
 
 fn main4() {
-    let rect1 = Rectanglo {
-        width: 30,
-        height: 50,
-    };
+    let rect1 = Rectanglo::sized(30, 50);
 
-    println!(
-        "The area of the rectangle is {} square pixels.",
-        rect1.area()
-    );
+    if let (Some(area), Some(border)) = (rect1.checked_area(), rect1.checked_border()) {
+        println!("The area of the rectangle is {} square pixels.", area);
 
-    println!(
-        "The border length of the  rectangle is {} pixels", rect1.border()
-    );
+        println!("The border length of the  rectangle is {} pixels", border);
+    }
 }
 
 
 // Methods with More Parameters:
 
 fn main5() {
-    let rect1 = Rectanglo {
-        width: 30,
-        height: 50,
-    };
-    let rect2 = Rectanglo {
-        width: 10,
-        height: 40,
-    };
-    let rect3 = Rectanglo {
-        width: 60,
-        height: 45,
-    };
+    let rect1 = Rectanglo::sized(30, 50);
+    let rect2 = Rectanglo::sized(10, 40);
+    let rect3 = Rectanglo::sized(60, 45);
 
     println!("Can rect1 hold rect2? {}", rect1.can_hold(&rect2));
     println!("Can rect1 hold rect3? {}", rect1.can_hold(&rect3));
+
+    render::print_table(&[rect1, rect2, rect3], &mut io::stdout()).expect("couldn't write to stdout");
 }
 
 // A note about method operators; 
@@ -231,3 +188,13 @@ fn main5() {
 // p1.distance(&p2);
 // (&p1).distance(&p2);
 
+
+// The borrow is spelled out on purpose, to show it's the same call.
+#[allow(clippy::needless_borrow)]
+fn main6() {
+    let p1 = Vec3(0, 0, 0);
+    let p2 = Vec3(3, 4, 12);
+
+    println!("p1 is {} away from p2", p1.distance(&p2));
+    println!("p1 is {} away from p2", (&p1).distance(&p2));
+}
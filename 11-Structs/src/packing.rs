@@ -0,0 +1,228 @@
+use crate::geometry::{Point, Rectanglo};
+
+// `can_hold` tells you whether one rectangle fits inside another. Packing
+// asks the bigger question: given a container and a pile of rectangles, where
+// should each one go so that as many as possible fit? It's what lays out UI
+// panels in a window, or sprites on a sprite sheet.
+
+// Two classic ways to do it:
+//
+// * Shelf packing sorts the items tallest first and lines them up left to
+//   right on shelves, starting a new shelf above the last when a row fills
+//   up. Fast and predictable, but it wastes the space above short items.
+//
+// * Guillotine packing keeps a list of free rectangles. Each item goes in the
+//   free rectangle it fits most snugly, and what's left of that rectangle is
+//   cut in two, as if with a guillotine. Slower, but it usually packs tighter.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Algorithm {
+    #[default]
+    Shelf,
+    Guillotine,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Options {
+    pub algorithm: Algorithm,
+    // Whether items may be turned 90 degrees to make them fit.
+    pub rotate: bool,
+}
+
+// Where item number `index` went. `rect` is in the container's coordinates,
+// and already turned if `rotated` is set.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement {
+    pub index: usize,
+    pub rect: Rectanglo,
+    pub rotated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Packing {
+    // Sorted by item index.
+    pub placements: Vec<Placement>,
+    // The indices of the items that didn't fit.
+    pub unplaced: Vec<usize>,
+    // How much of the container's area the placed items cover, from 0 to 100.
+    pub utilisation: f64,
+}
+
+// Packs `items` into `container`, going by the items' sizes only. Items are
+// placed at or after the container's origin; a container so far out that its
+// far edges don't fit in a `u32` has no room for anything.
+
+pub fn pack(container: &Rectanglo, items: &[Rectanglo], options: Options) -> Packing {
+    let mut placements = Vec::new();
+    let mut unplaced = Vec::new();
+
+    if container.right().is_some() && container.bottom().is_some() {
+        match options.algorithm {
+            Algorithm::Shelf => shelf(container, items, options.rotate, &mut placements, &mut unplaced),
+            Algorithm::Guillotine => {
+                guillotine(container, items, options.rotate, &mut placements, &mut unplaced)
+            }
+        }
+    } else {
+        unplaced.extend(0..items.len());
+    }
+
+    placements.sort_by_key(|p| p.index);
+    unplaced.sort_unstable();
+
+    let used: u64 = placements.iter().map(|p| area(&p.rect)).sum();
+    let utilisation = match area(container) {
+        0 => 0.0,
+        total => used as f64 * 100.0 / total as f64,
+    };
+
+    Packing {
+        placements,
+        unplaced,
+        utilisation,
+    }
+}
+
+// Areas in a `u64`, which a product of two `u32`s always fits.
+fn area(rect: &Rectanglo) -> u64 {
+    u64::from(rect.width) * u64::from(rect.height)
+}
+
+fn turned(rect: &Rectanglo) -> Rectanglo {
+    Rectanglo::sized(rect.height, rect.width)
+}
+
+// Unlike `can_hold`, which wants room to spare, an item that exactly fills
+// the space fits.
+fn fits(space: &Rectanglo, item: &Rectanglo) -> bool {
+    item.width <= space.width && item.height <= space.height
+}
+
+struct Shelf {
+    y: u32,
+    height: u32,
+    used: u32,
+}
+
+fn shelf(
+    container: &Rectanglo,
+    items: &[Rectanglo],
+    rotate: bool,
+    placements: &mut Vec<Placement>,
+    unplaced: &mut Vec<usize>,
+) {
+    // With rotation, items lie on their long side so the shelves stay low,
+    // unless that's too wide for the container.
+    let oriented = items.iter().map(|item| {
+        let upright = item.height > item.width || item.width > container.width;
+        if rotate && upright && item.height <= container.width {
+            (turned(item), true)
+        } else {
+            (*item, false)
+        }
+    });
+
+    let mut order: Vec<(usize, (Rectanglo, bool))> = oriented.enumerate().collect();
+    order.sort_by(|(_, (a, _)), (_, (b, _))| b.height.cmp(&a.height).then(b.width.cmp(&a.width)));
+
+    let mut shelves: Vec<Shelf> = Vec::new();
+    for (index, (item, rotated)) in order {
+        let room = |shelf: &Shelf| {
+            let space = Rectanglo::sized(container.width - shelf.used, shelf.height);
+            fits(&space, &item)
+        };
+
+        let found = shelves.iter().position(room);
+        let shelf = match found {
+            Some(i) => &mut shelves[i],
+            None => {
+                let y = shelves.last().map_or(0, |s| s.y + s.height);
+                let space = Rectanglo::sized(container.width, container.height - y);
+                if !fits(&space, &item) {
+                    unplaced.push(index);
+                    continue;
+                }
+                shelves.push(Shelf {
+                    y,
+                    height: item.height,
+                    used: 0,
+                });
+                shelves.last_mut().unwrap()
+            }
+        };
+
+        let origin = Point::new(container.origin.x + shelf.used, container.origin.y + shelf.y);
+        shelf.used += item.width;
+        placements.push(Placement {
+            index,
+            rect: Rectanglo::new(origin, item.width, item.height),
+            rotated,
+        });
+    }
+}
+
+fn guillotine(
+    container: &Rectanglo,
+    items: &[Rectanglo],
+    rotate: bool,
+    placements: &mut Vec<Placement>,
+    unplaced: &mut Vec<usize>,
+) {
+    let mut order: Vec<(usize, &Rectanglo)> = items.iter().enumerate().collect();
+    order.sort_by_key(|&(_, item)| std::cmp::Reverse(area(item)));
+
+    let mut free = vec![*container];
+    for (index, item) in order {
+        // The free rectangle with the least area left over, and which way
+        // round the item goes in it.
+        let mut best: Option<(u64, usize, Rectanglo, bool)> = None;
+        for (i, space) in free.iter().enumerate() {
+            let turns: &[bool] = if rotate { &[false, true] } else { &[false] };
+            for &rotated in turns {
+                let candidate = if rotated { turned(item) } else { *item };
+                if !fits(space, &candidate) {
+                    continue;
+                }
+                let waste = area(space) - area(&candidate);
+                if best.is_none_or(|(least, ..)| waste < least) {
+                    best = Some((waste, i, candidate, rotated));
+                }
+            }
+        }
+
+        let (i, item, rotated) = match best {
+            Some((_, i, item, rotated)) => (i, item, rotated),
+            None => {
+                unplaced.push(index);
+                continue;
+            }
+        };
+
+        let space = free.swap_remove(i);
+        let rect = Rectanglo::new(space.origin, item.width, item.height);
+        placements.push(Placement {
+            index,
+            rect,
+            rotated,
+        });
+
+        // Cut along the shorter leftover side, so the bigger of the two
+        // leftover pieces is as big as it can be.
+        let (x, y) = (space.origin.x, space.origin.y);
+        let (across, down) = (space.width - item.width, space.height - item.height);
+        let (right, below) = if across < down {
+            (
+                Rectanglo::new(Point::new(x + item.width, y), across, item.height),
+                Rectanglo::new(Point::new(x, y + item.height), space.width, down),
+            )
+        } else {
+            (
+                Rectanglo::new(Point::new(x + item.width, y), across, space.height),
+                Rectanglo::new(Point::new(x, y + item.height), item.width, down),
+            )
+        };
+
+        free.extend([right, below].iter().filter(|r| !r.is_empty()));
+    }
+}
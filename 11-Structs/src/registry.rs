@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::user::{build_user, User};
+
+// `build_user` will happily make a user out of any two strings. The registry
+// is the gatekeeper: it checks that emails and usernames look right, that
+// nobody else has them, and it keeps track of sign-ins and deactivations.
+
+pub const MIN_USERNAME: usize = 3;
+pub const MAX_USERNAME: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UserError {
+    InvalidEmail(String),
+    UsernameTooShort(String),
+    UsernameTooLong(String),
+    // The username, and the first character that isn't allowed in one.
+    UsernameCharacter(String, char),
+    EmailTaken(String),
+    UsernameTaken(String),
+    // Nobody has that username or email.
+    NotFound(String),
+    Inactive(String),
+}
+
+impl fmt::Display for UserError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UserError::InvalidEmail(email) => write!(f, "'{}' isn't a valid email address", email),
+            UserError::UsernameTooShort(name) => write!(
+                f,
+                "'{}' is too short for a username (at least {} characters)",
+                name, MIN_USERNAME
+            ),
+            UserError::UsernameTooLong(name) => write!(
+                f,
+                "'{}' is too long for a username (at most {} characters)",
+                name, MAX_USERNAME
+            ),
+            UserError::UsernameCharacter(name, c) => write!(
+                f,
+                "'{}' can't be a username because of '{}' (use letters, digits, '_', '-' and '.', starting with a letter)",
+                name, c
+            ),
+            UserError::EmailTaken(email) => write!(f, "{} is already registered", email),
+            UserError::UsernameTaken(name) => write!(f, "the username {} is taken", name),
+            UserError::NotFound(login) => write!(f, "there's no user called {}", login),
+            UserError::Inactive(name) => write!(f, "{} has been deactivated", name),
+        }
+    }
+}
+
+impl Error for UserError {}
+
+// Usernames are 3 to 32 ASCII letters, digits, '_', '-' or '.', and start
+// with a letter.
+
+pub fn validate_username(username: &str) -> Result<(), UserError> {
+    let length = username.chars().count();
+    if length < MIN_USERNAME {
+        return Err(UserError::UsernameTooShort(username.to_string()));
+    }
+    if length > MAX_USERNAME {
+        return Err(UserError::UsernameTooLong(username.to_string()));
+    }
+
+    let allowed = |(i, c): &(usize, char)| {
+        c.is_ascii_alphabetic() || (*i > 0 && (c.is_ascii_digit() || "_-.".contains(*c)))
+    };
+    match username.chars().enumerate().find(|ic| !allowed(ic)) {
+        Some((_, c)) => Err(UserError::UsernameCharacter(username.to_string(), c)),
+        None => Ok(()),
+    }
+}
+
+// Not the whole of RFC 5322, but what people actually type: a local part,
+// one '@', and a domain of at least two dot-separated labels, none of which
+// start or end with a '-'.
+
+pub fn validate_email(email: &str) -> Result<(), UserError> {
+    let invalid = || UserError::InvalidEmail(email.to_string());
+
+    let (local, domain) = email.split_once('@').ok_or_else(invalid)?;
+    let local_ok = !local.is_empty()
+        && !local.starts_with('.')
+        && !local.ends_with('.')
+        && !local.contains("..")
+        && local.chars().all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-/=?^_`{|}~.".contains(c));
+
+    let labels: Vec<&str> = domain.split('.').collect();
+    let domain_ok = labels.len() >= 2
+        && labels.iter().all(|label| {
+            !label.is_empty()
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        });
+
+    if local_ok && domain_ok {
+        Ok(())
+    } else {
+        Err(invalid())
+    }
+}
+
+// Usernames and emails are unique regardless of case, so the indexes are
+// keyed by the lowercased versions.
+
+#[derive(Debug, Default)]
+pub struct UserRegistry {
+    users: Vec<User>,
+    by_username: HashMap<String, usize>,
+    by_email: HashMap<String, usize>,
+}
+
+impl UserRegistry {
+    pub fn new() -> UserRegistry {
+        UserRegistry::default()
+    }
+
+    // Adds a user. They haven't signed in yet, so unlike a plain
+    // `build_user`, they start with a `sign_in_count` of 0.
+    pub fn register(&mut self, email: String, username: String) -> Result<&User, UserError> {
+        validate_email(&email)?;
+        validate_username(&username)?;
+
+        let email_key = email.to_lowercase();
+        let username_key = username.to_lowercase();
+        if self.by_email.contains_key(&email_key) {
+            return Err(UserError::EmailTaken(email));
+        }
+        if self.by_username.contains_key(&username_key) {
+            return Err(UserError::UsernameTaken(username));
+        }
+
+        let index = self.users.len();
+        self.users.push(User {
+            sign_in_count: 0,
+            ..build_user(email, username)
+        });
+        self.by_email.insert(email_key, index);
+        self.by_username.insert(username_key, index);
+
+        Ok(&self.users[index])
+    }
+
+    pub fn find_by_username(&self, username: &str) -> Option<&User> {
+        let index = self.by_username.get(&username.to_lowercase())?;
+        Some(&self.users[*index])
+    }
+
+    pub fn find_by_email(&self, email: &str) -> Option<&User> {
+        let index = self.by_email.get(&email.to_lowercase())?;
+        Some(&self.users[*index])
+    }
+
+    // `login` is either a username or an email address.
+    pub fn find(&self, login: &str) -> Option<&User> {
+        self.index_of(login).ok().map(|index| &self.users[index])
+    }
+
+    pub fn sign_in(&mut self, login: &str) -> Result<&User, UserError> {
+        let index = self.index_of(login)?;
+        let user = &mut self.users[index];
+        if !user.active {
+            return Err(UserError::Inactive(user.username.clone()));
+        }
+
+        user.sign_in_count += 1;
+        Ok(user)
+    }
+
+    pub fn deactivate(&mut self, login: &str) -> Result<&User, UserError> {
+        self.set_active(login, false)
+    }
+
+    pub fn reactivate(&mut self, login: &str) -> Result<&User, UserError> {
+        self.set_active(login, true)
+    }
+
+    pub fn len(&self) -> usize {
+        self.users.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.users.is_empty()
+    }
+
+    // In the order they registered.
+    pub fn iter(&self) -> impl Iterator<Item = &User> {
+        self.users.iter()
+    }
+
+    fn set_active(&mut self, login: &str, active: bool) -> Result<&User, UserError> {
+        let index = self.index_of(login)?;
+        let user = &mut self.users[index];
+        user.active = active;
+        Ok(user)
+    }
+
+    fn index_of(&self, login: &str) -> Result<usize, UserError> {
+        let key = login.to_lowercase();
+        let index = if login.contains('@') {
+            self.by_email.get(&key)
+        } else {
+            self.by_username.get(&key)
+        };
+
+        index.copied().ok_or_else(|| UserError::NotFound(login.to_string()))
+    }
+}
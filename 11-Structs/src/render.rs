@@ -0,0 +1,93 @@
+use std::env;
+use std::io::{self, Write};
+
+use crate::geometry::Rectanglo;
+
+// Drawing rectangles as text, for workshops where `{:#?}` output gets hard
+// to read.
+
+// The width of the terminal, going by `$COLUMNS`, or 80 if that isn't set.
+
+pub fn terminal_width() -> usize {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse().ok())
+        .filter(|&columns| columns > 0)
+        .unwrap_or(80)
+}
+
+// Draws `rect` as a box at most `columns` characters wide, one character per
+// unit unless it has to shrink to fit. Characters are about twice as tall as
+// they are wide, so the box gets half as many rows as it's high, to keep the
+// shape right. Tall rectangles shrink too, to at most `columns / 2` rows, so
+// the box is never taller than a square `columns` wide would be. Its size is
+// written in the middle when there's room.
+//
+//     +----------------------------+
+//     |                            |
+//     |           30x50            |
+//     |                            |
+//     ...
+
+pub fn draw<W: Write>(rect: &Rectanglo, columns: usize, output: &mut W) -> io::Result<()> {
+    if rect.is_empty() {
+        return writeln!(output, "(an empty {} rectangle)", rect);
+    }
+
+    let limit = columns.max(2) as f64;
+    let scale = (limit / f64::from(rect.width))
+        .min(limit / f64::from(rect.height))
+        .min(1.0);
+    let width = ((f64::from(rect.width) * scale).round() as usize).max(2);
+    let height = ((f64::from(rect.height) * scale / 2.0).round() as usize).max(2);
+
+    let label = format!("{}x{}", rect.width, rect.height);
+    let inside = width - 2;
+    let edge = format!("+{}+", "-".repeat(inside));
+
+    writeln!(output, "{}", edge)?;
+    for row in 1..height - 1 {
+        if row == (height - 1) / 2 && label.len() <= inside {
+            writeln!(output, "|{:^width$}|", label, width = inside)?;
+        } else {
+            writeln!(output, "|{}|", " ".repeat(inside))?;
+        }
+    }
+    writeln!(output, "{}", edge)
+}
+
+// A table of rectangles, numbered from 1. Areas and borders too big for a
+// `u32` show as "too big".
+//
+//       #       Width     Height         Area       Border
+//       1          30         50         1500          160
+
+pub fn print_table<W: Write>(rects: &[Rectanglo], output: &mut W) -> io::Result<()> {
+    let rows: Vec<[String; 2]> = rects
+        .iter()
+        .map(|rect| [measure(rect.checked_area()), measure(rect.checked_border())])
+        .collect();
+
+    writeln!(output, "{:>3}  {:>10} {:>10} {:>12} {:>12}", "#", "Width", "Height", "Area", "Border")?;
+
+    for (i, (rect, [area, border])) in rects.iter().zip(&rows).enumerate() {
+        writeln!(
+            output,
+            "{:>3}  {:>10} {:>10} {:>12} {:>12}",
+            i + 1,
+            rect.width,
+            rect.height,
+            area,
+            border
+        )?;
+    }
+
+    Ok(())
+}
+
+fn measure(value: Option<u32>) -> String {
+    match value {
+        Some(value) => value.to_string(),
+        None => String::from("too big"),
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, Write};
+
+use crate::cli::{self, Command};
+use crate::geometry::Rectanglo;
+
+// The calculator, interactively, with rectangles you can name and come back
+// to:
+//
+//     > let door = 30x50
+//     door = 30x50
+//     > can-hold door 10x40
+//     Can 30x50 hold 10x40? true
+//
+// Any command line command works, with names standing in for rectangles.
+
+pub const HELP: &str = "\
+  let <NAME> = <W>x<H>          name a rectangle (or: let <NAME> = <W> <H>,
+                                let <NAME> = square <SIZE>, let <NAME> = <OTHER>)
+  forget <NAME>                 forget a named rectangle
+  list                          show the named rectangles
+  area, border, can-hold, square, draw
+                                as on the command line; names work anywhere
+                                a rectangle does
+  help                          print this help
+  quit                          leave (so does end of input)";
+
+// Words that can't be used as names.
+const RESERVED: [&str; 13] = [
+    "area", "border", "can-hold", "square", "draw", "repl", "tour", "help", "let", "forget", "list",
+    "quit", "exit",
+];
+
+#[derive(Debug, Default)]
+pub struct Session {
+    rectangles: BTreeMap<String, Rectanglo>,
+}
+
+impl Session {
+    pub fn new() -> Session {
+        Session::default()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Rectanglo> {
+        self.rectangles.get(name)
+    }
+
+    // Runs one line. Mistakes are reported to `output` and the session
+    // carries on; `Ok(false)` means it's time to stop.
+    pub fn execute<W: Write>(&mut self, line: &str, output: &mut W) -> io::Result<bool> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+
+        let reply = match words.as_slice() {
+            [] => return Ok(true),
+            ["quit"] | ["exit"] => return Ok(false),
+            ["help"] => Ok(format!("{}\n", HELP)),
+            ["list"] => Ok(self.list()),
+            ["forget", name] => match self.rectangles.remove(*name) {
+                Some(_) => Ok(String::new()),
+                None => Err(format!("there's no rectangle called {}", name)),
+            },
+            ["let", name, "=", rest @ ..] => self.define(name, rest),
+            ["let", ..] => Err(String::from("expected let <NAME> = <W>x<H>")),
+            [name] if self.rectangles.contains_key(*name) => {
+                Ok(format!("{} = {}\n", name, self.rectangles[*name]))
+            }
+            _ => self.run(&words),
+        };
+
+        match reply {
+            Ok(text) => write!(output, "{}", text)?,
+            Err(message) => writeln!(output, "error: {}", message)?,
+        }
+        Ok(true)
+    }
+
+    fn define(&mut self, name: &str, words: &[&str]) -> Result<String, String> {
+        let valid = name.starts_with(|c: char| c.is_ascii_alphabetic())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && !RESERVED.contains(&name);
+        if !valid {
+            return Err(format!(
+                "'{}' can't be a name (use letters, digits and '_', starting with a letter, and not a command)",
+                name
+            ));
+        }
+
+        let words = self.substitute(words);
+        let rect = match words.as_slice() {
+            [square, size] if square == "square" => Rectanglo::square(cli::parse_length(size, "size")?),
+            [size] => cli::parse_size(size)?,
+            [width, height] => cli::parse_size(&format!("{}x{}", width, height))?,
+            _ => return Err(String::from("expected let <NAME> = <W>x<H>")),
+        };
+
+        self.rectangles.insert(name.to_string(), rect);
+        Ok(format!("{} = {}\n", name, rect))
+    }
+
+    fn run(&self, words: &[&str]) -> Result<String, String> {
+        // Sizes start with a digit, so anything starting with a letter is
+        // meant to be a name.
+        let unknown = words[1..]
+            .iter()
+            .find(|word| word.starts_with(|c: char| c.is_alphabetic()) && !self.rectangles.contains_key(**word));
+        if let Some(name) = unknown {
+            return Err(format!("there's no rectangle called {}", name));
+        }
+
+        let command = match Command::parse(self.substitute(words)) {
+            Ok(Command::Repl) => return Err(String::from("you're already in the REPL")),
+            Ok(Command::Tour) => return Err(String::from("the tour only runs from the command line")),
+            Ok(command) => command,
+            Err(message) => return Err(message.replace(cli::USAGE, "type help for the commands")),
+        };
+
+        let mut output = Vec::new();
+        cli::run(&command, &mut output).map_err(|e| e.to_string())?;
+        Ok(String::from_utf8_lossy(&output).into_owned())
+    }
+
+    // Swaps names for their sizes. The command itself is left alone.
+    fn substitute(&self, words: &[&str]) -> Vec<String> {
+        words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| match self.rectangles.get(*word) {
+                Some(rect) if i > 0 => format!("{}x{}", rect.width, rect.height),
+                _ => word.to_string(),
+            })
+            .collect()
+    }
+
+    fn list(&self) -> String {
+        if self.rectangles.is_empty() {
+            return String::from("No rectangles yet. Try: let door = 30x50\n");
+        }
+        self.rectangles
+            .iter()
+            .map(|(name, rect)| format!("{} = {}\n", name, rect))
+            .collect()
+    }
+}
+
+pub fn repl<I: BufRead, O: Write>(input: &mut I, output: &mut O) -> io::Result<()> {
+    let mut session = Session::new();
+    writeln!(output, "Rectangle calculator. Type help for the commands, quit to leave.")?;
+
+    loop {
+        write!(output, "> ")?;
+        output.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 || !session.execute(&line, output)? {
+            return Ok(());
+        }
+    }
+}
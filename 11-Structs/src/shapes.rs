@@ -0,0 +1,282 @@
+use crate::geometry::{Num, Point, Rect};
+
+// `Rectanglo` was the only thing with an area. A `Shape` is anything with an
+// area, a perimeter, a bounding box and a notion of which points are inside
+// it, so circles, triangles and polygons can be measured (and fitted inside
+// each other) the same way. Shapes are measured in `f64`.
+
+pub trait Shape {
+    fn area(&self) -> f64;
+    fn perimeter(&self) -> f64;
+    fn bounding_box(&self) -> Rect<f64>;
+    fn contains_point(&self, point: Point<f64>) -> bool;
+
+    // How far the shape reaches from the centre of its bounding box. Used to
+    // fit shapes inside circles.
+    fn bounding_radius(&self) -> f64 {
+        let b = self.bounding_box();
+        b.width.hypot(b.height) / 2.0
+    }
+
+    // Methods with More Parameters: can `other` fit inside, with room to
+    // spare? Only sizes count, not where either shape sits. `other`'s
+    // bounding box has to be strictly smaller, and its corners have to be
+    // inside this shape when both are centred. That's enough for a convex
+    // shape, where everything between the corners is inside too. Shapes
+    // with dents in them, like some polygons, have to check more.
+    fn can_hold(&self, other: &dyn Shape) -> bool {
+        match centred_box(&self.bounding_box(), &other.bounding_box()) {
+            Some(inner) => corners(&inner).iter().all(|&corner| self.contains_point(corner)),
+            None => false,
+        }
+    }
+}
+
+fn centre(rect: &Rect<f64>) -> Point<f64> {
+    Point::new(rect.origin.x + rect.width / 2.0, rect.origin.y + rect.height / 2.0)
+}
+
+// `inner` moved to the centre of `outer`, if it's strictly smaller both ways.
+fn centred_box(outer: &Rect<f64>, inner: &Rect<f64>) -> Option<Rect<f64>> {
+    if inner.width >= outer.width || inner.height >= outer.height {
+        return None;
+    }
+
+    let centre = centre(outer);
+    let origin = Point::new(centre.x - inner.width / 2.0, centre.y - inner.height / 2.0);
+    Some(Rect::new(origin, inner.width, inner.height))
+}
+
+fn corners(rect: &Rect<f64>) -> [Point<f64>; 4] {
+    let (left, top) = (rect.origin.x, rect.origin.y);
+    let (right, bottom) = (left + rect.width, top + rect.height);
+    [
+        Point::new(left, top),
+        Point::new(right, top),
+        Point::new(right, bottom),
+        Point::new(left, bottom),
+    ]
+}
+
+// Whether the line from `p` to `q` goes through the inside of `rect`, rather
+// than missing it or only running along its edges. The line is clipped to
+// the rectangle (the Liang-Barsky way, as the range of `t` for which
+// `p + t(q - p)` is inside), and the middle of what's left has to be strictly
+// inside.
+fn passes_through(rect: &Rect<f64>, p: Point<f64>, q: Point<f64>) -> bool {
+    let (left, top) = (rect.origin.x, rect.origin.y);
+    let (right, bottom) = (left + rect.width, top + rect.height);
+    let (dx, dy) = (q.x - p.x, q.y - p.y);
+
+    let (mut start, mut end) = (0.0, 1.0);
+    for &(step, room) in [(-dx, p.x - left), (dx, right - p.x), (-dy, p.y - top), (dy, bottom - p.y)].iter() {
+        if step == 0.0 {
+            if room < 0.0 {
+                return false;
+            }
+        } else if step < 0.0 {
+            start = f64::max(start, room / step);
+        } else {
+            end = f64::min(end, room / step);
+        }
+    }
+    if start > end {
+        return false;
+    }
+
+    let middle = (start + end) / 2.0;
+    let (x, y) = (p.x + middle * dx, p.y + middle * dy);
+    left < x && x < right && top < y && y < bottom
+}
+
+fn distance(a: Point<f64>, b: Point<f64>) -> f64 {
+    (a.x - b.x).hypot(a.y - b.y)
+}
+
+// The z component of the cross product of `a -> b` and `a -> c`. Positive if
+// `c` is to the left of `a -> b`, and twice the area of the triangle `abc`.
+fn cross(a: Point<f64>, b: Point<f64>, c: Point<f64>) -> f64 {
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+fn bounding_box(points: &[Point<f64>]) -> Rect<f64> {
+    let first = match points.first() {
+        Some(&first) => Rect::new(first, 0.0, 0.0),
+        None => return Rect::default(),
+    };
+
+    points.iter().fold(first, |b, p| {
+        let left = b.origin.x.min(p.x);
+        let top = b.origin.y.min(p.y);
+        let right = (b.origin.x + b.width).max(p.x);
+        let bottom = (b.origin.y + b.height).max(p.y);
+        Rect::new(Point::new(left, top), right - left, bottom - top)
+    })
+}
+
+impl<T: Num + Into<f64>> Shape for Rect<T> {
+    fn area(&self) -> f64 {
+        self.width.into() * self.height.into()
+    }
+
+    fn perimeter(&self) -> f64 {
+        (self.width.into() + self.height.into()) * 2.0
+    }
+
+    fn bounding_box(&self) -> Rect<f64> {
+        Rect::new(
+            Point::new(self.origin.x.into(), self.origin.y.into()),
+            self.width.into(),
+            self.height.into(),
+        )
+    }
+
+    fn contains_point(&self, point: Point<f64>) -> bool {
+        self.bounding_box().contains_point(point)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Circle {
+    pub centre: Point<f64>,
+    pub radius: f64,
+}
+
+impl Circle {
+    pub fn new(centre: Point<f64>, radius: f64) -> Circle {
+        Circle {
+            centre,
+            radius: radius.max(0.0),
+        }
+    }
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        std::f64::consts::PI * self.radius * self.radius
+    }
+
+    fn perimeter(&self) -> f64 {
+        2.0 * std::f64::consts::PI * self.radius
+    }
+
+    fn bounding_box(&self) -> Rect<f64> {
+        Rect::new(
+            Point::new(self.centre.x - self.radius, self.centre.y - self.radius),
+            self.radius * 2.0,
+            self.radius * 2.0,
+        )
+    }
+
+    fn contains_point(&self, point: Point<f64>) -> bool {
+        distance(self.centre, point) <= self.radius
+    }
+
+    fn bounding_radius(&self) -> f64 {
+        self.radius
+    }
+
+    // Anything whose bounding radius is smaller than ours fits.
+    fn can_hold(&self, other: &dyn Shape) -> bool {
+        other.bounding_radius() < self.radius
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Triangle {
+    pub a: Point<f64>,
+    pub b: Point<f64>,
+    pub c: Point<f64>,
+}
+
+impl Triangle {
+    pub fn new(a: Point<f64>, b: Point<f64>, c: Point<f64>) -> Triangle {
+        Triangle { a, b, c }
+    }
+}
+
+impl Shape for Triangle {
+    fn area(&self) -> f64 {
+        cross(self.a, self.b, self.c).abs() / 2.0
+    }
+
+    fn perimeter(&self) -> f64 {
+        distance(self.a, self.b) + distance(self.b, self.c) + distance(self.c, self.a)
+    }
+
+    fn bounding_box(&self) -> Rect<f64> {
+        bounding_box(&[self.a, self.b, self.c])
+    }
+
+    // Inside (or on an edge) if the point is on the same side of all three
+    // edges, whichever way round the corners go.
+    fn contains_point(&self, point: Point<f64>) -> bool {
+        let sides = [
+            cross(self.a, self.b, point),
+            cross(self.b, self.c, point),
+            cross(self.c, self.a, point),
+        ];
+
+        sides.iter().all(|&s| s >= 0.0) || sides.iter().all(|&s| s <= 0.0)
+    }
+}
+
+// A polygon is its corners in order, either way round. The last corner joins
+// back up to the first.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon {
+    pub points: Vec<Point<f64>>,
+}
+
+impl Polygon {
+    pub fn new(points: Vec<Point<f64>>) -> Polygon {
+        Polygon { points }
+    }
+
+    // Each corner paired with the next one, wrapping around at the end.
+    fn edges(&self) -> impl Iterator<Item = (Point<f64>, Point<f64>)> + '_ {
+        let next = self.points.iter().cycle().skip(1);
+        self.points.iter().copied().zip(next.copied())
+    }
+}
+
+impl Shape for Polygon {
+    // The shoelace formula.
+    fn area(&self) -> f64 {
+        let twice: f64 = self.edges().map(|(p, q)| p.x * q.y - q.x * p.y).sum();
+        twice.abs() / 2.0
+    }
+
+    fn perimeter(&self) -> f64 {
+        self.edges().map(|(p, q)| distance(p, q)).sum()
+    }
+
+    fn bounding_box(&self) -> Rect<f64> {
+        bounding_box(&self.points)
+    }
+
+    // Casts a ray to the right of `point` and counts the edges it crosses:
+    // an odd number means the point is inside.
+    fn contains_point(&self, point: Point<f64>) -> bool {
+        self.edges()
+            .filter(|&(p, q)| (p.y > point.y) != (q.y > point.y))
+            .filter(|&(p, q)| point.x < p.x + (point.y - p.y) * (q.x - p.x) / (q.y - p.y))
+            .count()
+            % 2
+            == 1
+    }
+
+    // A polygon can be concave, so the corners being inside isn't enough:
+    // a U's arms hold the corners of a box that sits across the gap between
+    // them. If none of the edges cut into the box as well, it's all inside.
+    fn can_hold(&self, other: &dyn Shape) -> bool {
+        let inner = match centred_box(&self.bounding_box(), &other.bounding_box()) {
+            Some(inner) => inner,
+            None => return false,
+        };
+
+        corners(&inner).iter().all(|&corner| self.contains_point(corner))
+            && !self.edges().any(|(p, q)| passes_through(&inner, p, q))
+    }
+}
@@ -0,0 +1,225 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::user::User;
+
+// Users written down somewhere, one per line as JSON (JSON Lines), or as a
+// CSV table with a header row:
+//
+//     {"username":"someusername123","email":"someone@example.com","sign_in_count":1,"active":true}
+//
+//     username,email,sign_in_count,active
+//     someusername123,someone@example.com,1,true
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    JsonLines,
+    Csv,
+}
+
+impl Format {
+    // Goes by the file extension: `.csv` is CSV, `.jsonl`, `.ndjson` and
+    // `.json` are JSON Lines.
+    pub fn from_path(path: &Path) -> Option<Format> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "csv" => Some(Format::Csv),
+            "jsonl" | "ndjson" | "json" => Some(Format::JsonLines),
+            _ => None,
+        }
+    }
+}
+
+// Things that can go wrong reading or writing users. JSON line numbers count
+// from 1; CSV errors carry their own position.
+
+#[derive(Debug)]
+pub enum StoreError {
+    Io(io::Error),
+    Json { line: usize, error: serde_json::Error },
+    Csv(csv::Error),
+    UnknownFormat(PathBuf),
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StoreError::Io(e) => write!(f, "could not access the user file: {}", e),
+            StoreError::Json { line, error } => write!(f, "bad user on line {}: {}", line, error),
+            StoreError::Csv(e) => write!(f, "bad user CSV: {}", e),
+            StoreError::UnknownFormat(path) => write!(
+                f,
+                "can't tell what format {} is in (use .jsonl or .csv)",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl Error for StoreError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            StoreError::Io(e) => Some(e),
+            StoreError::Json { error, .. } => Some(error),
+            StoreError::Csv(e) => Some(e),
+            StoreError::UnknownFormat(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for StoreError {
+    fn from(e: io::Error) -> Self {
+        StoreError::Io(e)
+    }
+}
+
+impl From<csv::Error> for StoreError {
+    fn from(e: csv::Error) -> Self {
+        StoreError::Csv(e)
+    }
+}
+
+const CSV_HEADER: [&str; 4] = ["username", "email", "sign_in_count", "active"];
+
+pub fn read_users<R: Read>(reader: R, format: Format) -> Result<Vec<User>, StoreError> {
+    match format {
+        Format::JsonLines => {
+            let mut users = Vec::new();
+            for (i, line) in BufReader::new(reader).lines().enumerate() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let user = serde_json::from_str(&line)
+                    .map_err(|error| StoreError::Json { line: i + 1, error })?;
+                users.push(user);
+            }
+            Ok(users)
+        }
+        Format::Csv => {
+            let mut reader = csv::Reader::from_reader(reader);
+            let users = reader.deserialize().collect::<Result<_, _>>()?;
+            Ok(users)
+        }
+    }
+}
+
+pub fn write_users<W: Write>(mut writer: W, format: Format, users: &[User]) -> Result<(), StoreError> {
+    match format {
+        Format::JsonLines => {
+            for user in users {
+                serde_json::to_writer(&mut writer, user).map_err(io::Error::from)?;
+                writeln!(writer)?;
+            }
+            writer.flush()?;
+        }
+        Format::Csv => {
+            // The header is written by hand so that even an empty file has one.
+            let mut writer = csv::WriterBuilder::new().has_headers(false).from_writer(writer);
+            writer.write_record(CSV_HEADER)?;
+            for user in users {
+                writer.serialize(user)?;
+            }
+            writer.flush()?;
+        }
+    }
+
+    Ok(())
+}
+
+// Somewhere users can be loaded from and saved to.
+
+pub trait UserStore {
+    fn load(&self) -> Result<Vec<User>, StoreError>;
+
+    // Replaces whatever was stored before.
+    fn save(&mut self, users: &[User]) -> Result<(), StoreError>;
+}
+
+// Keeps the users in memory, which is handy for tests.
+
+#[derive(Debug, Default, Clone)]
+pub struct MemoryStore {
+    users: Vec<User>,
+}
+
+impl MemoryStore {
+    pub fn new() -> MemoryStore {
+        MemoryStore::default()
+    }
+}
+
+impl UserStore for MemoryStore {
+    fn load(&self) -> Result<Vec<User>, StoreError> {
+        Ok(self.users.clone())
+    }
+
+    fn save(&mut self, users: &[User]) -> Result<(), StoreError> {
+        self.users = users.to_vec();
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FileStore {
+    path: PathBuf,
+    format: Format,
+}
+
+impl FileStore {
+    pub fn new<P: Into<PathBuf>>(path: P, format: Format) -> FileStore {
+        FileStore {
+            path: path.into(),
+            format,
+        }
+    }
+
+    // Works out the format from the file extension.
+    pub fn open<P: Into<PathBuf>>(path: P) -> Result<FileStore, StoreError> {
+        let path = path.into();
+        match Format::from_path(&path) {
+            Some(format) => Ok(FileStore::new(path, format)),
+            None => Err(StoreError::UnknownFormat(path)),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn format(&self) -> Format {
+        self.format
+    }
+}
+
+impl UserStore for FileStore {
+    // A missing file just means nobody's been saved yet.
+    fn load(&self) -> Result<Vec<User>, StoreError> {
+        match fs::File::open(&self.path) {
+            Ok(file) => read_users(file, self.format),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    // Writes to a temporary file first and then renames it over the real
+    // one, so a crash halfway through never leaves a half-written file.
+    fn save(&mut self, users: &[User]) -> Result<(), StoreError> {
+        if let Some(dir) = self.path.parent() {
+            if !dir.as_os_str().is_empty() {
+                fs::create_dir_all(dir)?;
+            }
+        }
+
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+
+        write_users(io::BufWriter::new(fs::File::create(&tmp)?), self.format, users)?;
+        fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
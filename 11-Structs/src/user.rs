@@ -0,0 +1,170 @@
+use std::error::Error;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+// A basic struct:
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct User {
+    pub username: String,
+    pub email: String,
+    pub sign_in_count: u64,
+    pub active: bool,
+}
+
+// Returning a struct from a function:
+
+pub fn build_user(email: String, username: String) -> User {
+
+    // Because the parameter names and the struct field names are exactly 
+    // the same in, we can use the field init shorthand syntax
+
+    User {
+        email,
+        username,
+        active: true,
+        sign_in_count: 1,
+    }
+
+    // Or, to conSTRUCT in a static fashion:
+
+    // let user1 = User {
+    //     email: String::from("someone@example.com"),
+    //     username: String::from("someusername123"),
+    //     active: true,
+    //     sign_in_count: 1,
+    // };
+
+
+    // Using struct update syntax, we can achieve the same effect with less code:
+
+    // let user2 = User {
+    //     email: String::from("another@example.com"),
+    //     username: String::from("anotherusername567"),
+    //     ..user1        // remaining values are set from the previous instance of User
+    // };
+
+}
+
+// `User::default()` is the template the builder starts from: the same
+// active, signed-in-once user that `build_user` makes, minus the email and
+// username. That makes it usable in struct update syntax too:
+//
+//     let user3 = User {
+//         email: String::from("third@example.com"),
+//         username: String::from("thirdusername"),
+//         ..User::default()
+//     };
+
+impl Default for User {
+    fn default() -> User {
+        User {
+            username: String::new(),
+            email: String::new(),
+            sign_in_count: 1,
+            active: true,
+        }
+    }
+}
+
+impl User {
+    pub fn builder() -> UserBuilder {
+        UserBuilder::new()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildError {
+    MissingEmail,
+    MissingUsername,
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::MissingEmail => write!(f, "a user needs an email address"),
+            BuildError::MissingUsername => write!(f, "a user needs a username"),
+        }
+    }
+}
+
+impl Error for BuildError {}
+
+// Builds users a field at a time. Email and username have to be given; the
+// rest come from the template (`User::default()` unless you pick another)
+// unless they're overridden.
+//
+// `build` borrows the builder, so one builder can stamp out lots of users.
+// `stamp(n)` does the same but swaps `{n}` in the email and username for
+// `n`, which is handy for test fixtures:
+//
+//     let builder = User::builder().email("user{n}@example.com").username("user{n}");
+//     let users: Vec<User> = (0..100).map(|n| builder.stamp(n).unwrap()).collect();
+
+#[derive(Debug, Clone, Default)]
+pub struct UserBuilder {
+    template: User,
+    email: Option<String>,
+    username: Option<String>,
+    sign_in_count: Option<u64>,
+    active: Option<bool>,
+}
+
+impl UserBuilder {
+    pub fn new() -> UserBuilder {
+        UserBuilder::default()
+    }
+
+    // The template's email and username are ignored: every user needs their
+    // own.
+    pub fn from_template(template: &User) -> UserBuilder {
+        UserBuilder {
+            template: template.clone(),
+            ..UserBuilder::default()
+        }
+    }
+
+    pub fn email<S: Into<String>>(mut self, email: S) -> UserBuilder {
+        self.email = Some(email.into());
+        self
+    }
+
+    pub fn username<S: Into<String>>(mut self, username: S) -> UserBuilder {
+        self.username = Some(username.into());
+        self
+    }
+
+    pub fn sign_in_count(mut self, sign_in_count: u64) -> UserBuilder {
+        self.sign_in_count = Some(sign_in_count);
+        self
+    }
+
+    pub fn active(mut self, active: bool) -> UserBuilder {
+        self.active = Some(active);
+        self
+    }
+
+    pub fn build(&self) -> Result<User, BuildError> {
+        let email = self.email.clone().ok_or(BuildError::MissingEmail)?;
+        let username = self.username.clone().ok_or(BuildError::MissingUsername)?;
+
+        Ok(User {
+            email,
+            username,
+            sign_in_count: self.sign_in_count.unwrap_or(self.template.sign_in_count),
+            active: self.active.unwrap_or(self.template.active),
+        })
+    }
+
+    pub fn stamp(&self, n: usize) -> Result<User, BuildError> {
+        let number = n.to_string();
+        let user = self.build()?;
+
+        Ok(User {
+            email: user.email.replace("{n}", &number),
+            username: user.username.replace("{n}", &number),
+            ..user
+        })
+    }
+}
@@ -0,0 +1,94 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+// The chapter's `Point(i32, i32, i32)` tuple struct as a 3D vector, generic
+// over the number type. It's called `Vec3` so it isn't confused with
+// `geometry::Point`, the 2D corner of a rectangle. The arithmetic works for
+// any number that supports it; lengths and distances come out as `f64`,
+// working from the coordinates converted to `f64` first, so large integer
+// points can't overflow.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Vec3<T = i32>(pub T, pub T, pub T);
+
+impl<T: Copy + Add<Output = T> + Mul<Output = T>> Vec3<T> {
+    pub fn dot(&self, other: &Vec3<T>) -> T {
+        self.0 * other.0 + self.1 * other.1 + self.2 * other.2
+    }
+}
+
+impl<T: Copy + Sub<Output = T> + Mul<Output = T>> Vec3<T> {
+    // At right angles to both `self` and `other`, following the right-hand
+    // rule.
+    pub fn cross(&self, other: &Vec3<T>) -> Vec3<T> {
+        Vec3(
+            self.1 * other.2 - self.2 * other.1,
+            self.2 * other.0 - self.0 * other.2,
+            self.0 * other.1 - self.1 * other.0,
+        )
+    }
+}
+
+impl<T: Copy + Into<f64>> Vec3<T> {
+    pub fn to_f64(&self) -> Vec3<f64> {
+        Vec3(self.0.into(), self.1.into(), self.2.into())
+    }
+
+    pub fn length(&self) -> f64 {
+        let p = self.to_f64();
+        p.dot(&p).sqrt()
+    }
+
+    // The same direction, with a length of 1. The zero vector doesn't have a
+    // direction, so it gives `None`.
+    pub fn normalize(&self) -> Option<Vec3<f64>> {
+        let length = self.length();
+        if length == 0.0 || !length.is_finite() {
+            return None;
+        }
+        Some(self.to_f64() / length)
+    }
+
+    pub fn distance(&self, other: &Vec3<T>) -> f64 {
+        (self.to_f64() - other.to_f64()).length()
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vec3<T> {
+    type Output = Vec3<T>;
+
+    fn add(self, other: Vec3<T>) -> Vec3<T> {
+        Vec3(self.0 + other.0, self.1 + other.1, self.2 + other.2)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vec3<T> {
+    type Output = Vec3<T>;
+
+    fn sub(self, other: Vec3<T>) -> Vec3<T> {
+        Vec3(self.0 - other.0, self.1 - other.1, self.2 - other.2)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Vec3<T> {
+    type Output = Vec3<T>;
+
+    fn mul(self, scalar: T) -> Vec3<T> {
+        Vec3(self.0 * scalar, self.1 * scalar, self.2 * scalar)
+    }
+}
+
+impl<T: Copy + Div<Output = T>> Div<T> for Vec3<T> {
+    type Output = Vec3<T>;
+
+    fn div(self, scalar: T) -> Vec3<T> {
+        Vec3(self.0 / scalar, self.1 / scalar, self.2 / scalar)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vec3<T> {
+    type Output = Vec3<T>;
+
+    fn neg(self) -> Vec3<T> {
+        Vec3(-self.0, -self.1, -self.2)
+    }
+}
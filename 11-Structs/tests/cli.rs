@@ -0,0 +1,118 @@
+use std::io::Cursor;
+
+use structs::cli::{self, Command};
+use structs::geometry::Rectanglo;
+use structs::repl::{self, Session};
+
+fn parse(args: &[&str]) -> Result<Command, String> {
+    Command::parse(args.iter().map(|s| s.to_string()))
+}
+
+fn run(args: &[&str]) -> String {
+    let mut output = Vec::new();
+    cli::run(&parse(args).unwrap(), &mut output).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn commands() {
+    assert_eq!(parse(&["area", "30", "50"]), Ok(Command::Area(Rectanglo::sized(30, 50))));
+    assert_eq!(parse(&["area", "30x50"]), Ok(Command::Area(Rectanglo::sized(30, 50))));
+    assert_eq!(parse(&["border", "30X50"]), Ok(Command::Border(Rectanglo::sized(30, 50))));
+    assert_eq!(
+        parse(&["can-hold", "30x50", "10x40"]),
+        Ok(Command::CanHold(Rectanglo::sized(30, 50), Rectanglo::sized(10, 40)))
+    );
+    assert_eq!(parse(&["square", "3"]), Ok(Command::Square(3)));
+    assert_eq!(parse(&["repl"]), Ok(Command::Repl));
+}
+
+#[test]
+fn malformed_dimensions() {
+    assert_eq!(
+        parse(&["area", "30x"]),
+        Err(String::from("'' isn't a valid height (expected a whole number from 0 to 4294967295)"))
+    );
+    assert_eq!(
+        parse(&["area", "-3", "4"]),
+        Err(String::from("'-3' isn't a valid width (expected a whole number from 0 to 4294967295)"))
+    );
+    assert_eq!(parse(&["area", "30"]), Err(String::from("area needs a height after the width 30")));
+    assert_eq!(parse(&["border"]), Err(String::from("border needs a rectangle, e.g. border 30x50")));
+    assert_eq!(
+        parse(&["can-hold", "30x50", "10x40", "5x5"]),
+        Err(String::from("unexpected argument '5x5' after can-hold"))
+    );
+    assert!(parse(&["square", "3.5"]).unwrap_err().contains("'3.5' isn't a valid size"));
+    assert!(parse(&["volume", "1"]).unwrap_err().starts_with("unknown command 'volume'"));
+    assert_eq!(parse(&[]), Err(String::from(cli::USAGE)));
+}
+
+#[test]
+fn output() {
+    assert_eq!(run(&["area", "30", "50"]), "The area of the rectangle is 1500 square pixels.\n");
+    assert_eq!(run(&["border", "30x50"]), "The border length of the rectangle is 160 pixels.\n");
+    assert_eq!(run(&["can-hold", "30x50", "10x40"]), "Can 30x50 hold 10x40? true\n");
+    assert_eq!(run(&["can-hold", "30x50", "60x45"]), "Can 30x50 hold 60x45? false\n");
+    assert_eq!(
+        run(&["area", "4294967295x2"]),
+        "4294967295x2 is too big to measure: its area doesn't fit in a u32.\n"
+    );
+    assert!(run(&["square", "3"]).contains("          3          3            9           12"));
+}
+
+fn session(script: &str) -> String {
+    let mut output = Vec::new();
+    repl::repl(&mut Cursor::new(script.as_bytes().to_vec()), &mut output).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn repl_keeps_named_rectangles() {
+    let transcript = session("let door = 30x50\nlet card = 10 40\nlet tile = square 3\ncan-hold door card\narea tile\nlist\n");
+
+    let expected = "\
+Rectangle calculator. Type help for the commands, quit to leave.
+> door = 30x50
+> card = 10x40
+> tile = 3x3
+> Can 30x50 hold 10x40? true
+> The area of the rectangle is 9 square pixels.
+> card = 10x40
+door = 30x50
+tile = 3x3
+> ";
+    assert_eq!(transcript, expected);
+}
+
+#[test]
+fn repl_reports_errors_and_carries_on() {
+    let transcript = session("let 1x = 2x2\nlet area = 2x2\narea door\nforget door\nlet a = 2y2\nwhat\nrepl\nquit\narea 2x2\n");
+
+    let errors: Vec<&str> = transcript.lines().filter(|l| l.contains("error:")).collect();
+    assert_eq!(errors.len(), 7, "{}", transcript);
+    assert!(errors[0].contains("'1x' can't be a name"));
+    assert!(errors[1].contains("'area' can't be a name"));
+    assert!(errors[2].contains("there's no rectangle called door"));
+    assert!(errors[3].contains("there's no rectangle called door"));
+    assert!(errors[4].contains("'2y2' isn't a size"));
+    assert!(errors[5].contains("unknown command 'what'"));
+    assert!(errors[6].contains("already in the REPL"));
+
+    // Nothing runs after quit.
+    assert!(!transcript.contains("square pixels"));
+}
+
+#[test]
+fn names_can_be_redefined_and_copied() {
+    let mut session = Session::new();
+    let mut output = Vec::new();
+
+    for line in &["let a = 2x2", "let b = a", "let a = 5x5", "forget b"] {
+        assert!(session.execute(line, &mut output).unwrap());
+    }
+
+    assert_eq!(session.get("a"), Some(&Rectanglo::square(5)));
+    assert_eq!(session.get("b"), None);
+    assert!(!session.execute("exit", &mut output).unwrap());
+}
@@ -0,0 +1,143 @@
+use std::fs;
+use std::path::PathBuf;
+
+use structs::color::{Color, ColorError, Hsl, Hsv};
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-3
+}
+
+#[test]
+fn parsing() {
+    assert_eq!("#ff8800".parse(), Ok(Color(255, 136, 0)));
+    assert_eq!("#F80".parse(), Ok(Color(255, 136, 0)));
+    assert_eq!("rgb(255, 136, 0)".parse(), Ok(Color(255, 136, 0)));
+    assert_eq!(" RGB(0,0,0) ".parse(), Ok(Color::BLACK));
+    assert_eq!("Orange".parse(), Ok(Color(255, 165, 0)));
+    assert_eq!("grey".parse::<Color>(), "gray".parse());
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!("rgb(256, 0, 0)".parse::<Color>(), Err(ColorError::OutOfRange(256)));
+    assert_eq!("rgb(-1, 0, 0)".parse::<Color>(), Err(ColorError::OutOfRange(-1)));
+    assert_eq!(
+        "chartreuse".parse::<Color>(),
+        Err(ColorError::UnknownName(String::from("chartreuse")))
+    );
+
+    for bad in &["#ff88", "#ff880", "#ff880000", "#gg8800", "rgb(1, 2)", "rgb(1, 2, x)", "", "1,2,3"] {
+        assert_eq!(bad.parse::<Color>(), Err(ColorError::Syntax(bad.to_string())), "{}", bad);
+    }
+}
+
+#[test]
+fn checked_construction() {
+    assert_eq!(Color::new(1, 2, 3), Ok(Color(1, 2, 3)));
+    assert_eq!(Color::new(1, 2, 300), Err(ColorError::OutOfRange(300)));
+}
+
+#[test]
+fn hex_with_alpha() {
+    assert_eq!(Color::from_hex_alpha("#00000000"), Ok((Color::BLACK, 0)));
+    assert_eq!(Color::from_hex_alpha("#ff880080"), Ok((Color(255, 136, 0), 128)));
+    assert_eq!(Color::from_hex_alpha("#f808"), Ok((Color(255, 136, 0), 136)));
+    assert_eq!(Color::from_hex_alpha("#123456"), Ok((Color(0x12, 0x34, 0x56), 255)));
+}
+
+#[test]
+fn display_round_trips() {
+    for &color in &[Color::BLACK, Color::WHITE, Color(1, 128, 254)] {
+        assert_eq!(color.to_string().parse(), Ok(color));
+    }
+    assert_eq!(Color(255, 136, 0).to_string(), "#ff8800");
+}
+
+#[test]
+fn hsl_and_hsv() {
+    let orange = Color(255, 136, 0);
+    let hsl = orange.to_hsl();
+    assert!(close(hsl.h, 32.0) && close(hsl.s, 1.0) && close(hsl.l, 0.5), "{:?}", hsl);
+
+    let hsv = Color(64, 128, 128).to_hsv();
+    assert!(close(hsv.h, 180.0) && close(hsv.s, 0.5) && close(hsv.v, 128.0 / 255.0), "{:?}", hsv);
+
+    assert_eq!(Color::from_hsl(Hsl { h: 240.0, s: 1.0, l: 0.25 }), Color(0, 0, 128));
+    assert_eq!(Color::from_hsv(Hsv { h: -60.0, s: 1.0, v: 1.0 }), Color(255, 0, 255));
+    assert_eq!(Color::from_hsl(Hsl { h: 123.0, s: 0.0, l: 1.0 }), Color::WHITE);
+}
+
+#[test]
+fn conversions_round_trip_every_grey_and_a_spread_of_colors() {
+    for r in (0..=255).step_by(15) {
+        for g in (0..=255).step_by(17) {
+            for b in (0..=255).step_by(51) {
+                let color = Color(r, g, b);
+                assert_eq!(Color::from_hsl(color.to_hsl()), color);
+                assert_eq!(Color::from_hsv(color.to_hsv()), color);
+            }
+        }
+    }
+}
+
+#[test]
+fn mixing() {
+    assert_eq!(Color::BLACK.mix(Color::WHITE, 0.5), Color(128, 128, 128));
+    assert_eq!(Color::BLACK.mix(Color::WHITE, 0.0), Color::BLACK);
+    assert_eq!(Color::BLACK.mix(Color::WHITE, 7.0), Color::WHITE);
+    assert_eq!(Color(255, 0, 0).blend_over(Color(0, 0, 255), 255), Color(255, 0, 0));
+    assert_eq!(Color(255, 0, 0).blend_over(Color(0, 0, 255), 0), Color(0, 0, 255));
+}
+
+#[test]
+fn contrast() {
+    assert!(close(Color::BLACK.contrast_ratio(Color::WHITE), 21.0));
+    assert!(close(Color::WHITE.contrast_ratio(Color::BLACK), 21.0));
+    assert!(close(Color(119, 119, 119).contrast_ratio(Color::WHITE), 4.478));
+    assert!(close(Color(255, 0, 0).contrast_ratio(Color(255, 0, 0)), 1.0));
+}
+
+// Every color in the VS Code theme customizations (and the palettes in
+// their comments) has to parse.
+
+fn theme_colors(name: &str) -> Vec<(usize, String)> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../vscode-themes").join(name);
+    let text = fs::read_to_string(path).unwrap();
+
+    let mut colors = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        for (start, _) in line.match_indices('#') {
+            let hex: String = line[start + 1..].chars().take_while(|c| c.is_alphanumeric()).collect();
+            colors.push((i + 1, format!("#{}", hex)));
+        }
+    }
+    colors
+}
+
+#[test]
+fn theme_palettes_are_valid() {
+    for name in &["nightowl.json", "zenburn.json"] {
+        let colors = theme_colors(name);
+        assert!(colors.len() > 20, "{} has only {} colors", name, colors.len());
+
+        for (line, hex) in colors {
+            if let Err(e) = Color::from_hex_alpha(&hex) {
+                panic!("{} line {}: {}", name, line, e);
+            }
+        }
+    }
+}
+
+#[test]
+fn theme_text_is_readable() {
+    let pairs = [
+        ("#8BADC1", "#001122"), // Night Owl side bar
+        ("#8FB28F", "#2B2B2B"), // Zenburn status bar
+        ("#FFFFEF", "#3F3F3F"), // Zenburn cursor over the editor
+    ];
+
+    for (fg, bg) in pairs.iter() {
+        let ratio = fg.parse::<Color>().unwrap().contrast_ratio(bg.parse().unwrap());
+        assert!(ratio >= 4.5, "{} on {} is only {:.2}:1", fg, bg, ratio);
+    }
+}
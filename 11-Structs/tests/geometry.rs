@@ -0,0 +1,78 @@
+use structs::geometry::{Point, Rect, Rectanglo};
+use structs::shapes::Shape;
+
+#[test]
+fn rectanglo_keeps_its_chapter_methods() {
+    let rect1 = Rectanglo::sized(30, 50);
+
+    assert_eq!(rect1.checked_area(), Some(1500));
+    assert_eq!(rect1.checked_border(), Some(160));
+    assert!(rect1.can_hold(&Rectanglo::sized(10, 40)));
+    assert!(!rect1.can_hold(&Rectanglo::sized(60, 45)));
+    assert!(!rect1.can_hold(&rect1));
+    assert_eq!(Rectanglo::square(3), Rectanglo::sized(3, 3));
+}
+
+#[test]
+fn overflow_gives_none() {
+    let big = Rect::sized(u32::MAX, 2);
+
+    assert_eq!(big.checked_area(), None);
+    assert_eq!(big.checked_border(), None);
+    assert_eq!(big.scale(2), None);
+    assert_eq!(big.translate(1, 0).and_then(|r| r.right()), None);
+    assert_eq!(Rect::sized(f32::MAX, 2.0).checked_area(), None);
+}
+
+#[test]
+fn contains_points_on_the_near_edges_only() {
+    let rect = Rect::new(Point::new(-2, -2), 4, 4);
+
+    assert!(rect.contains_point(Point::new(-2, -2)));
+    assert!(rect.contains_point(Point::new(1, 1)));
+    assert!(!rect.contains_point(Point::new(2, 0)));
+    assert!(!rect.contains_point(Point::new(0, -3)));
+}
+
+#[test]
+fn contains_rects() {
+    let outer = Rect::new(Point::new(0, 0), 10, 10);
+
+    assert!(outer.contains(&outer));
+    assert!(outer.contains(&Rect::new(Point::new(2, 3), 8, 7)));
+    assert!(!outer.contains(&Rect::new(Point::new(2, 3), 9, 7)));
+    assert!(!outer.contains(&Rect::new(Point::new(-1, 0), 2, 2)));
+}
+
+#[test]
+fn intersection_and_union() {
+    let a = Rect::new(Point::new(0, 0), 10, 10);
+    let b = Rect::new(Point::new(5, -5), 10, 10);
+
+    assert_eq!(a.intersection(&b), Some(Rect::new(Point::new(5, 0), 5, 5)));
+    assert_eq!(a.union(&b), Some(Rect::new(Point::new(0, -5), 15, 15)));
+
+    // Sharing an edge isn't overlapping.
+    let c = Rect::new(Point::new(10, 0), 5, 5);
+    assert_eq!(a.intersection(&c), None);
+    assert_eq!(a.union(&c), Some(Rect::new(Point::new(0, 0), 15, 10)));
+}
+
+#[test]
+fn translate_and_scale() {
+    let rect = Rect::new(Point::new(1.5, 2.0), 3.0, 4.0);
+
+    assert_eq!(rect.translate(-1.5, 1.0), Some(Rect::new(Point::new(0.0, 3.0), 3.0, 4.0)));
+    assert_eq!(rect.scale(0.5), Some(Rect::new(Point::new(1.5, 2.0), 1.5, 2.0)));
+    assert_eq!(Rect::sized(2u8, 3).translate(250, 0), Some(Rect::new(Point::new(250, 0), 2, 3)));
+    assert_eq!(Rect::sized(2u8, 3).translate(255, 0).unwrap().right(), None);
+}
+
+#[test]
+fn negative_sizes_are_clamped() {
+    let rect = Rect::sized(-3, 4);
+
+    assert_eq!(rect.width, 0);
+    assert!(rect.is_empty());
+    assert_eq!(rect.checked_area(), Some(0));
+}
@@ -0,0 +1,124 @@
+use structs::geometry::{Point, Rectanglo};
+use structs::packing::{self, Algorithm, Options, Packing};
+
+const ALGORITHMS: [Algorithm; 2] = [Algorithm::Shelf, Algorithm::Guillotine];
+
+// Every placement is inside the container, the right size, and clear of
+// every other placement.
+fn check(container: &Rectanglo, items: &[Rectanglo], packing: &Packing) {
+    for (n, placement) in packing.placements.iter().enumerate() {
+        let item = items[placement.index];
+        let (width, height) = if placement.rotated {
+            (item.height, item.width)
+        } else {
+            (item.width, item.height)
+        };
+
+        assert_eq!((placement.rect.width, placement.rect.height), (width, height));
+        assert!(container.contains(&placement.rect), "{:?} is outside", placement);
+
+        for other in &packing.placements[n + 1..] {
+            assert_eq!(placement.rect.intersection(&other.rect), None);
+        }
+    }
+
+    let mut seen: Vec<usize> = packing.placements.iter().map(|p| p.index).collect();
+    seen.extend(&packing.unplaced);
+    seen.sort_unstable();
+    assert_eq!(seen, (0..items.len()).collect::<Vec<_>>());
+}
+
+#[test]
+fn exact_fit_fills_the_container() {
+    let container = Rectanglo::sized(30, 50);
+    let items = [
+        Rectanglo::sized(30, 20),
+        Rectanglo::sized(10, 30),
+        Rectanglo::sized(20, 30),
+    ];
+
+    for &algorithm in &ALGORITHMS {
+        let options = Options { algorithm, rotate: false };
+        let packing = packing::pack(&container, &items, options);
+
+        check(&container, &items, &packing);
+        assert!(packing.unplaced.is_empty(), "{:?}", algorithm);
+        assert!((packing.utilisation - 100.0).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn items_that_dont_fit_are_reported() {
+    let container = Rectanglo::sized(10, 10);
+    let items = [
+        Rectanglo::sized(6, 6),
+        Rectanglo::sized(6, 6),
+        Rectanglo::sized(11, 1),
+        Rectanglo::sized(4, 4),
+    ];
+
+    for &algorithm in &ALGORITHMS {
+        let packing = packing::pack(&container, &items, Options { algorithm, rotate: true });
+
+        check(&container, &items, &packing);
+        assert!(packing.unplaced.contains(&2));
+        assert_eq!(packing.unplaced.len(), 2, "{:?}", algorithm);
+        assert!((packing.utilisation - 52.0).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn rotation_makes_room() {
+    let container = Rectanglo::sized(50, 10);
+    let items = [Rectanglo::sized(10, 50)];
+
+    for &algorithm in &ALGORITHMS {
+        let stiff = packing::pack(&container, &items, Options { algorithm, rotate: false });
+        assert_eq!(stiff.unplaced, vec![0]);
+        assert_eq!(stiff.utilisation, 0.0);
+
+        let packing = packing::pack(&container, &items, Options { algorithm, rotate: true });
+        assert!(packing.placements[0].rotated);
+        assert_eq!(packing.placements[0].rect, Rectanglo::sized(50, 10));
+    }
+}
+
+#[test]
+fn placements_follow_the_container_origin() {
+    let container = Rectanglo::new(Point::new(100, 200), 20, 20);
+    let items = [Rectanglo::square(10), Rectanglo::square(10), Rectanglo::square(10)];
+
+    for &algorithm in &ALGORITHMS {
+        let packing = packing::pack(&container, &items, Options { algorithm, rotate: false });
+
+        check(&container, &items, &packing);
+        assert!(packing.placements.iter().any(|p| p.rect.origin == Point::new(100, 200)));
+        assert!((packing.utilisation - 75.0).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn sprite_sheet() {
+    let container = Rectanglo::sized(256, 256);
+    let items: Vec<Rectanglo> = (0..60)
+        .map(|i| Rectanglo::sized(8 + (i * 7) % 40, 8 + (i * 13) % 36))
+        .collect();
+
+    for &algorithm in &ALGORITHMS {
+        for &rotate in &[false, true] {
+            let packing = packing::pack(&container, &items, Options { algorithm, rotate });
+            check(&container, &items, &packing);
+            assert!(packing.utilisation > 50.0, "{:?} only used {}%", algorithm, packing.utilisation);
+        }
+    }
+}
+
+#[test]
+fn empty_inputs() {
+    let packing = packing::pack(&Rectanglo::sized(0, 10), &[Rectanglo::sized(1, 1)], Options::default());
+    assert_eq!(packing.unplaced, vec![0]);
+    assert_eq!(packing.utilisation, 0.0);
+
+    let packing = packing::pack(&Rectanglo::sized(10, 10), &[], Options::default());
+    assert!(packing.placements.is_empty());
+}
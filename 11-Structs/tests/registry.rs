@@ -0,0 +1,126 @@
+use structs::registry::{self, UserError, UserRegistry};
+
+fn registry() -> UserRegistry {
+    let mut registry = UserRegistry::new();
+    registry
+        .register(String::from("someone@example.com"), String::from("someusername123"))
+        .unwrap();
+    registry
+        .register(String::from("another@example.com"), String::from("anotherusername567"))
+        .unwrap();
+    registry
+}
+
+#[test]
+fn registered_users_start_active_and_unsigned_in() {
+    let registry = registry();
+    let user = registry.find_by_username("someusername123").unwrap();
+
+    assert_eq!(user.email, "someone@example.com");
+    assert!(user.active);
+    assert_eq!(user.sign_in_count, 0);
+    assert_eq!(registry.len(), 2);
+}
+
+#[test]
+fn emails() {
+    for good in &["a@b.co", "first.last+tag@mail.example.org", "x_y@sub-domain.example"] {
+        assert_eq!(registry::validate_email(good), Ok(()), "{}", good);
+    }
+
+    for bad in &[
+        "",
+        "plain",
+        "@example.com",
+        "someone@",
+        "someone@localhost",
+        "some one@example.com",
+        "a@@example.com",
+        ".a@example.com",
+        "a..b@example.com",
+        "a@-example.com",
+        "a@example..com",
+    ] {
+        assert_eq!(
+            registry::validate_email(bad),
+            Err(UserError::InvalidEmail(bad.to_string())),
+            "{}",
+            bad
+        );
+    }
+}
+
+#[test]
+fn usernames() {
+    assert_eq!(registry::validate_username("ab_c-1.2"), Ok(()));
+    assert_eq!(
+        registry::validate_username("ab"),
+        Err(UserError::UsernameTooShort(String::from("ab")))
+    );
+    assert_eq!(
+        registry::validate_username(&"a".repeat(33)),
+        Err(UserError::UsernameTooLong("a".repeat(33)))
+    );
+    assert_eq!(
+        registry::validate_username("1abc"),
+        Err(UserError::UsernameCharacter(String::from("1abc"), '1'))
+    );
+    assert_eq!(
+        registry::validate_username("ab cd"),
+        Err(UserError::UsernameCharacter(String::from("ab cd"), ' '))
+    );
+}
+
+#[test]
+fn uniqueness_ignores_case() {
+    let mut registry = registry();
+
+    assert_eq!(
+        registry.register(String::from("SOMEONE@example.com"), String::from("newname")),
+        Err(UserError::EmailTaken(String::from("SOMEONE@example.com")))
+    );
+    assert_eq!(
+        registry.register(String::from("new@example.com"), String::from("SomeUsername123")),
+        Err(UserError::UsernameTaken(String::from("SomeUsername123")))
+    );
+    assert_eq!(registry.len(), 2);
+}
+
+#[test]
+fn sign_in_by_username_or_email() {
+    let mut registry = registry();
+
+    assert_eq!(registry.sign_in("someusername123").unwrap().sign_in_count, 1);
+    assert_eq!(registry.sign_in("Someone@Example.com").unwrap().sign_in_count, 2);
+    assert_eq!(registry.find("anotherusername567").unwrap().sign_in_count, 0);
+    assert_eq!(
+        registry.sign_in("nobody"),
+        Err(UserError::NotFound(String::from("nobody")))
+    );
+}
+
+#[test]
+fn deactivated_users_cannot_sign_in() {
+    let mut registry = registry();
+
+    assert!(!registry.deactivate("another@example.com").unwrap().active);
+    assert_eq!(
+        registry.sign_in("anotherusername567"),
+        Err(UserError::Inactive(String::from("anotherusername567")))
+    );
+
+    registry.reactivate("anotherusername567").unwrap();
+    assert_eq!(registry.sign_in("anotherusername567").unwrap().sign_in_count, 1);
+}
+
+#[test]
+fn errors_explain_themselves() {
+    assert_eq!(
+        UserError::UsernameTooShort(String::from("ab")).to_string(),
+        "'ab' is too short for a username (at least 3 characters)"
+    );
+    assert_eq!(
+        UserError::InvalidEmail(String::from("plain")).to_string(),
+        "'plain' isn't a valid email address"
+    );
+}
@@ -0,0 +1,78 @@
+use structs::geometry::{Point, Rect, Rectanglo};
+use structs::render;
+
+fn drawn(rect: &Rectanglo, columns: usize) -> String {
+    let mut output = Vec::new();
+    render::draw(rect, columns, &mut output).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn display() {
+    assert_eq!(Rectanglo::sized(30, 50).to_string(), "30x50");
+    assert_eq!(Rect::new(Point::new(-1, 2), 3, 4).to_string(), "3x4 at (-1, 2)");
+    assert_eq!(Rect::sized(1.5, 2.0).to_string(), "1.5x2");
+}
+
+#[test]
+fn draws_at_one_character_per_unit() {
+    let expected = "\
++--------+
+|        |
+| 10x10  |
+|        |
++--------+
+";
+    assert_eq!(drawn(&Rectanglo::square(10), 80), expected);
+}
+
+#[test]
+fn shrinks_to_fit_the_terminal() {
+    let picture = drawn(&Rectanglo::sized(300, 100), 60);
+    let lines: Vec<&str> = picture.lines().collect();
+
+    assert!(lines.iter().all(|line| line.chars().count() == 60));
+    // 100 units scaled by 60/300 is 20 columns' worth, so 10 rows.
+    assert_eq!(lines.len(), 10);
+    assert!(picture.contains("300x100"));
+}
+
+#[test]
+fn shrinks_tall_rectangles_to_fit_too() {
+    // 200 units high would be 100 rows; 80 columns allow 40.
+    let picture = drawn(&Rectanglo::sized(1, 200), 80);
+    let lines: Vec<&str> = picture.lines().collect();
+    assert_eq!(lines.len(), 40);
+    assert!(lines.iter().all(|line| line.chars().count() == 2));
+
+    // Scaled to fit the height, a 50x400 box is 10 columns wide.
+    let picture = drawn(&Rectanglo::sized(50, 400), 80);
+    assert_eq!(picture.lines().count(), 40);
+    assert!(picture.lines().all(|line| line.chars().count() == 10));
+
+    assert_eq!(drawn(&Rectanglo::sized(1, 4_000_000_000), 80).lines().count(), 40);
+}
+
+#[test]
+fn tiny_and_empty_rectangles() {
+    assert_eq!(drawn(&Rectanglo::square(1), 80), "++\n++\n");
+    assert_eq!(drawn(&Rectanglo::sized(0, 5), 80), "(an empty 0x5 rectangle)\n");
+}
+
+#[test]
+fn table() {
+    let mut output = Vec::new();
+    let rects = [
+        Rectanglo::sized(30, 50),
+        Rectanglo::sized(10, 40),
+        Rectanglo::sized(u32::MAX, 2),
+    ];
+    render::print_table(&rects, &mut output).unwrap();
+
+    let expected = "  #       Width     Height         Area       Border
+  1          30         50         1500          160
+  2          10         40          400          100
+  3  4294967295          2      too big      too big
+";
+    assert_eq!(String::from_utf8(output).unwrap(), expected);
+}
@@ -0,0 +1,134 @@
+use structs::geometry::{Point, Rect, Rectanglo};
+use structs::shapes::{Circle, Polygon, Shape, Triangle};
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+fn p(x: f64, y: f64) -> Point<f64> {
+    Point::new(x, y)
+}
+
+#[test]
+fn rectanglo_as_a_shape() {
+    let rect = Rectanglo::sized(30, 50);
+
+    assert!(close(rect.area(), 1500.0));
+    assert!(close(rect.perimeter(), 160.0));
+    assert_eq!(rect.bounding_box(), Rect::sized(30.0, 50.0));
+    assert!(Shape::contains_point(&rect, p(29.5, 0.0)));
+    assert!(!Shape::contains_point(&rect, p(30.0, 0.0)));
+}
+
+#[test]
+fn circle_measurements() {
+    let circle = Circle::new(p(1.0, 1.0), 2.0);
+
+    assert!(close(circle.area(), std::f64::consts::PI * 4.0));
+    assert!(close(circle.perimeter(), std::f64::consts::PI * 4.0));
+    assert_eq!(circle.bounding_box(), Rect::new(p(-1.0, -1.0), 4.0, 4.0));
+    assert!(circle.contains_point(p(3.0, 1.0)));
+    assert!(!circle.contains_point(p(2.5, 2.5)));
+}
+
+#[test]
+fn triangle_measurements() {
+    let triangle = Triangle::new(p(0.0, 0.0), p(4.0, 0.0), p(0.0, 3.0));
+
+    assert!(close(triangle.area(), 6.0));
+    assert!(close(triangle.perimeter(), 12.0));
+    assert_eq!(triangle.bounding_box(), Rect::new(p(0.0, 0.0), 4.0, 3.0));
+    assert!(triangle.contains_point(p(1.0, 1.0)));
+    assert!(triangle.contains_point(p(2.0, 0.0)));
+    assert!(!triangle.contains_point(p(3.0, 3.0)));
+
+    // Clockwise corners work too.
+    let flipped = Triangle::new(p(0.0, 0.0), p(0.0, 3.0), p(4.0, 0.0));
+    assert!(flipped.contains_point(p(1.0, 1.0)));
+}
+
+#[test]
+fn polygon_uses_the_shoelace_formula() {
+    // An L shape, which isn't convex.
+    let l = Polygon::new(vec![
+        p(0.0, 0.0),
+        p(4.0, 0.0),
+        p(4.0, 1.0),
+        p(1.0, 1.0),
+        p(1.0, 3.0),
+        p(0.0, 3.0),
+    ]);
+
+    assert!(close(l.area(), 6.0));
+    assert!(close(l.perimeter(), 14.0));
+    assert_eq!(l.bounding_box(), Rect::new(p(0.0, 0.0), 4.0, 3.0));
+    assert!(l.contains_point(p(0.5, 2.5)));
+    assert!(l.contains_point(p(3.5, 0.5)));
+    assert!(!l.contains_point(p(2.0, 2.0)));
+
+    let square = Polygon::new(vec![p(0.0, 0.0), p(0.0, 2.0), p(2.0, 2.0), p(2.0, 0.0)]);
+    assert!(close(square.area(), 4.0));
+    assert!(close(Polygon::new(vec![]).area(), 0.0));
+}
+
+#[test]
+fn can_hold_across_shapes() {
+    let rect = Rectanglo::sized(30, 50);
+    let circle = Circle::new(p(0.0, 0.0), 10.0);
+    let triangle = Triangle::new(p(0.0, 0.0), p(10.0, 0.0), p(5.0, 8.0));
+
+    assert!(rect.can_hold(&Rectanglo::sized(10, 40)));
+    assert!(!rect.can_hold(&Rectanglo::sized(60, 45)));
+    assert!(rect.can_hold(&circle));
+    assert!(!circle.can_hold(&rect));
+
+    // A 14x14 square's corners are 9.9 from its centre, a 15x15 one's 10.6.
+    assert!(circle.can_hold(&Rectanglo::square(14)));
+    assert!(!circle.can_hold(&Rectanglo::square(15)));
+    assert!(circle.can_hold(&Circle::new(p(50.0, 50.0), 9.0)));
+    assert!(!circle.can_hold(&circle));
+
+    assert!(circle.can_hold(&triangle));
+    assert!(!triangle.can_hold(&circle));
+    assert!(triangle.can_hold(&Rectanglo::square(2)));
+    assert!(!triangle.can_hold(&Rectanglo::sized(9, 1)));
+}
+
+#[test]
+fn concave_polygons_check_their_edges() {
+    // A U, open at the top, with a 10x20 notch cut out of the middle.
+    let u = Polygon::new(vec![
+        p(0.0, 0.0),
+        p(30.0, 0.0),
+        p(30.0, 30.0),
+        p(20.0, 30.0),
+        p(20.0, 10.0),
+        p(10.0, 10.0),
+        p(10.0, 30.0),
+        p(0.0, 30.0),
+    ]);
+
+    // A 25x25 box's corners all land in the U, but its middle is in the
+    // notch. A small box in the middle is all notch.
+    assert!(!u.can_hold(&Rect::sized(25.0, 25.0)));
+    assert!(!u.can_hold(&Rect::sized(4.0, 4.0)));
+
+    // Without the notch the same square fits.
+    let square = Polygon::new(vec![p(0.0, 0.0), p(30.0, 0.0), p(30.0, 30.0), p(0.0, 30.0)]);
+    assert!(square.can_hold(&Rect::sized(25.0, 25.0)));
+
+    // A shallower notch leaves room for a flat box under it, but not a
+    // taller one.
+    let wide = Polygon::new(vec![
+        p(0.0, 0.0),
+        p(30.0, 0.0),
+        p(30.0, 30.0),
+        p(20.0, 30.0),
+        p(20.0, 20.0),
+        p(10.0, 20.0),
+        p(10.0, 30.0),
+        p(0.0, 30.0),
+    ]);
+    assert!(wide.can_hold(&Rect::sized(20.0, 4.0)));
+    assert!(!wide.can_hold(&Rect::sized(20.0, 12.0)));
+}
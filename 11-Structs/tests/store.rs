@@ -0,0 +1,129 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use structs::store::{self, FileStore, Format, MemoryStore, StoreError, UserStore};
+use structs::user::{build_user, User};
+
+fn fixtures() -> Vec<User> {
+    vec![
+        build_user(String::from("someone@example.com"), String::from("someusername123")),
+        User {
+            sign_in_count: 42,
+            active: false,
+            ..build_user(String::from("another@example.com"), String::from("anotherusername567"))
+        },
+        User {
+            sign_in_count: 0,
+            ..build_user(String::from("ob@example.com"), String::from("o'brien"))
+        },
+    ]
+}
+
+// Awkward values that need quoting or escaping in one format or the other.
+fn awkward() -> Vec<User> {
+    vec![
+        build_user(String::from("comma,quote\"@example.com"), String::from("line\nbreak")),
+        build_user(String::from(""), String::from("ünïcödé {json}")),
+        User {
+            sign_in_count: u64::MAX,
+            ..build_user(String::from(" spaced "), String::from("\"quoted\""))
+        },
+    ]
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("structs-store-{}-{}", std::process::id(), name));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name)
+}
+
+#[test]
+fn fixtures_load_straight_into_users() {
+    for name in &["users.jsonl", "users.csv"] {
+        let store = FileStore::open(fixture(name)).unwrap();
+        assert_eq!(store.load().unwrap(), fixtures(), "{}", name);
+    }
+}
+
+#[test]
+fn round_trips_through_memory() {
+    let mut store = MemoryStore::new();
+    assert!(store.load().unwrap().is_empty());
+
+    store.save(&awkward()).unwrap();
+    assert_eq!(store.load().unwrap(), awkward());
+}
+
+#[test]
+fn round_trips_through_both_formats() {
+    for &format in &[Format::JsonLines, Format::Csv] {
+        for users in &[fixtures(), awkward(), Vec::new()] {
+            let mut bytes = Vec::new();
+            store::write_users(&mut bytes, format, users).unwrap();
+            assert_eq!(&store::read_users(&bytes[..], format).unwrap(), users, "{:?}", format);
+        }
+    }
+}
+
+#[test]
+fn round_trips_through_files() {
+    for name in &["users.jsonl", "users.csv"] {
+        let path = temp_path(name);
+        let mut store = FileStore::open(&path).unwrap();
+
+        assert!(store.load().unwrap().is_empty());
+        store.save(&awkward()).unwrap();
+        assert_eq!(store.load().unwrap(), awkward());
+
+        // Saving replaces what was there.
+        store.save(&fixtures()).unwrap();
+        assert_eq!(store.load().unwrap(), fixtures());
+        fs::remove_file(&path).unwrap();
+    }
+}
+
+#[test]
+fn converts_between_formats() {
+    let path = temp_path("converted.csv");
+    let jsonl = FileStore::open(fixture("users.jsonl")).unwrap();
+    let mut csv = FileStore::open(&path).unwrap();
+
+    csv.save(&jsonl.load().unwrap()).unwrap();
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        fs::read_to_string(fixture("users.csv")).unwrap()
+    );
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn empty_csv_still_has_a_header() {
+    let mut bytes = Vec::new();
+    store::write_users(&mut bytes, Format::Csv, &[]).unwrap();
+    assert_eq!(String::from_utf8(bytes).unwrap(), "username,email,sign_in_count,active\n");
+}
+
+#[test]
+fn bad_input_is_reported() {
+    let jsonl = "{\"username\":\"a\",\"email\":\"b\",\"sign_in_count\":1,\"active\":true}\n{\"username\":\"c\"}\n";
+    match store::read_users(jsonl.as_bytes(), Format::JsonLines) {
+        Err(StoreError::Json { line: 2, .. }) => {}
+        other => panic!("expected a JSON error on line 2, got {:?}", other),
+    }
+
+    let csv = "username,email,sign_in_count,active\na,b,lots,true\n";
+    match store::read_users(csv.as_bytes(), Format::Csv) {
+        Err(StoreError::Csv(_)) => {}
+        other => panic!("expected a CSV error, got {:?}", other),
+    }
+
+    match FileStore::open("users.txt") {
+        Err(StoreError::UnknownFormat(path)) => assert_eq!(path, PathBuf::from("users.txt")),
+        other => panic!("expected an unknown format, got {:?}", other),
+    }
+}
@@ -0,0 +1,74 @@
+use structs::user::{build_user, BuildError, User, UserBuilder};
+
+#[test]
+fn builder_defaults_match_build_user() {
+    let built = User::builder()
+        .email("someone@example.com")
+        .username("someusername123")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        built,
+        build_user(String::from("someone@example.com"), String::from("someusername123"))
+    );
+}
+
+#[test]
+fn required_fields() {
+    assert_eq!(User::builder().username("a").build(), Err(BuildError::MissingEmail));
+    assert_eq!(User::builder().email("a@b.c").build(), Err(BuildError::MissingUsername));
+    assert_eq!(BuildError::MissingEmail.to_string(), "a user needs an email address");
+}
+
+#[test]
+fn overrides_beat_the_template() {
+    let template = User {
+        sign_in_count: 7,
+        active: false,
+        ..User::default()
+    };
+
+    let user = UserBuilder::from_template(&template)
+        .email("a@b.c")
+        .username("abc")
+        .build()
+        .unwrap();
+    assert_eq!((user.sign_in_count, user.active), (7, false));
+
+    let user = UserBuilder::from_template(&template)
+        .email("a@b.c")
+        .username("abc")
+        .active(true)
+        .sign_in_count(0)
+        .build()
+        .unwrap();
+    assert_eq!((user.sign_in_count, user.active), (0, true));
+}
+
+#[test]
+fn template_email_and_username_are_not_inherited() {
+    let template = build_user(String::from("someone@example.com"), String::from("someusername123"));
+
+    assert_eq!(
+        UserBuilder::from_template(&template).build(),
+        Err(BuildError::MissingEmail)
+    );
+}
+
+#[test]
+fn stamping_out_users() {
+    let builder = User::builder()
+        .email("user{n}@example.com")
+        .username("user{n}")
+        .sign_in_count(0);
+    let users: Vec<User> = (0..100).map(|n| builder.stamp(n).unwrap()).collect();
+
+    assert_eq!(users.len(), 100);
+    assert_eq!(users[42].email, "user42@example.com");
+    assert_eq!(users[42].username, "user42");
+    assert!(users.iter().all(|u| u.sign_in_count == 0 && u.active));
+
+    // Building again without stamping leaves the pattern alone.
+    assert_eq!(builder.build().unwrap().username, "user{n}");
+}
@@ -0,0 +1,64 @@
+use structs::vector::Vec3;
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn arithmetic() {
+    let a = Vec3(1, 2, 3);
+    let b = Vec3(4, -5, 6);
+
+    assert_eq!(a + b, Vec3(5, -3, 9));
+    assert_eq!(a - b, Vec3(-3, 7, -3));
+    assert_eq!(a * 3, Vec3(3, 6, 9));
+    assert_eq!(b / 2, Vec3(2, -2, 3));
+    assert_eq!(-a, Vec3(-1, -2, -3));
+    assert_eq!(Vec3(0.5, 1.0, 1.5) * 2.0, Vec3(1.0, 2.0, 3.0));
+}
+
+#[test]
+fn dot_and_cross() {
+    let x = Vec3(1, 0, 0);
+    let y = Vec3(0, 1, 0);
+    let z = Vec3(0, 0, 1);
+
+    assert_eq!(x.cross(&y), z);
+    assert_eq!(y.cross(&x), -z);
+    assert_eq!(x.dot(&y), 0);
+    assert_eq!(Vec3(1, 2, 3).dot(&Vec3(4, -5, 6)), 12);
+
+    // The cross product is at right angles to both.
+    let (a, b) = (Vec3(2, 3, 4), Vec3(5, 6, 7));
+    let c = a.cross(&b);
+    assert_eq!((c.dot(&a), c.dot(&b)), (0, 0));
+}
+
+#[test]
+fn length_and_distance() {
+    let p1 = Vec3(0, 0, 0);
+    let p2 = Vec3(3, 4, 12);
+
+    assert!(close(p2.length(), 13.0));
+    assert!(close(p1.distance(&p2), 13.0));
+    assert!(close(p1.distance(&p2), p2.distance(&p1)));
+    assert!(close(Vec3(1.5f32, 0.0, 2.0).length(), 2.5));
+}
+
+#[test]
+fn distance_does_not_overflow() {
+    let far = Vec3(i32::MAX, i32::MAX, i32::MAX);
+    let near = Vec3(i32::MIN, i32::MIN, i32::MIN);
+
+    let expected = (f64::from(i32::MAX) - f64::from(i32::MIN)) * 3f64.sqrt();
+    assert!((far.distance(&near) - expected).abs() < 1.0);
+}
+
+#[test]
+fn normalize() {
+    let unit = Vec3(0, 3, 4).normalize().unwrap();
+
+    assert!(close(unit.length(), 1.0));
+    assert!(close(unit.1, 0.6) && close(unit.2, 0.8));
+    assert_eq!(Vec3(0, 0, 0).normalize(), None);
+}
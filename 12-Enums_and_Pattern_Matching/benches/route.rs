@@ -0,0 +1,69 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use enums_and_pattern_matching::cidr::Cidr;
+use enums_and_pattern_matching::ip::{IpAddr, IpAddrKind};
+use enums_and_pattern_matching::route::{NextHop, RoutingTable};
+
+// Lookups in a table of 100,000 routes, about the size of a small slice of
+// the internet's routing table. Run with `cargo bench --bench route`.
+
+const ROUTES: usize = 100_000;
+const LOOKUPS: usize = 1_000;
+
+// The same "random" numbers every run, without pulling in a crate for it.
+struct Numbers(u64);
+
+impl Numbers {
+    fn next(&mut self) -> u64 {
+        // Knuth's MMIX linear congruential generator.
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        self.0
+    }
+}
+
+fn table(kind: IpAddrKind, numbers: &mut Numbers) -> RoutingTable {
+    let mut table = RoutingTable::new();
+    let gateway = NextHop::Interface(String::from("eth0"));
+
+    while table.len() < ROUTES {
+        let bits = u128::from(numbers.next()) << 64 | u128::from(numbers.next());
+        // Mostly /16 to /24 for V4 and /32 to /48 for V6, like real tables.
+        let prefix = match kind {
+            IpAddrKind::V4 => 16 + numbers.next() % 9,
+            IpAddrKind::V6 => 32 + numbers.next() % 17,
+        };
+        let block = Cidr::new(IpAddr::from_bits(kind, bits), prefix as u32).unwrap();
+        table.insert(block, gateway.clone());
+    }
+
+    table
+}
+
+fn addresses(kind: IpAddrKind, numbers: &mut Numbers) -> Vec<IpAddr> {
+    (0..LOOKUPS)
+        .map(|_| {
+            let bits = u128::from(numbers.next()) << 64 | u128::from(numbers.next());
+            IpAddr::from_bits(kind, bits)
+        })
+        .collect()
+}
+
+fn lookup(c: &mut Criterion) {
+    let mut numbers = Numbers(2021);
+
+    for &(name, kind) in [("v4", IpAddrKind::V4), ("v6", IpAddrKind::V6)].iter() {
+        let table = table(kind, &mut numbers);
+        let addresses = addresses(kind, &mut numbers);
+
+        c.bench_function(&format!("lookup {} in 100k routes (x{})", name, LOOKUPS), |b| {
+            b.iter(|| {
+                for address in &addresses {
+                    black_box(table.lookup(black_box(address)));
+                }
+            })
+        });
+    }
+}
+
+criterion_group!(benches, lookup);
+criterion_main!(benches);
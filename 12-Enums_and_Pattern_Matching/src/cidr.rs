@@ -0,0 +1,287 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::ip::{IpAddr, IpAddrKind, IpParseError};
+
+// A block of addresses written the CIDR way, as an address and how many of
+// its leading bits are fixed: `10.0.0.0/8` is every address starting 10.
+//
+// A `Cidr` always holds the block's network address, so `10.1.2.3/8` parses
+// as `10.0.0.0/8`. That makes two ways of writing the same block compare
+// equal, and sorting puts blocks in address order (V4 before V6).
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Cidr {
+    network: IpAddr,
+    prefix: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CidrError {
+    MissingPrefix(String),
+    InvalidPrefix(String),
+    Address(IpParseError),
+    PrefixTooLong { prefix: u32, kind: IpAddrKind },
+    PrefixTooShort { prefix: u32, min: u8 },
+}
+
+impl fmt::Display for CidrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CidrError::MissingPrefix(s) => {
+                write!(f, "'{}' has no prefix length (expected ADDRESS/LENGTH, e.g. 10.0.0.0/8)", s)
+            }
+            CidrError::InvalidPrefix(s) => write!(f, "'{}' isn't a valid prefix length", s),
+            CidrError::Address(e) => write!(f, "{}", e),
+            CidrError::PrefixTooLong { prefix, kind } => write!(
+                f,
+                "/{} is too long: {:?} prefixes go up to /{}",
+                prefix,
+                kind,
+                kind.bits()
+            ),
+            CidrError::PrefixTooShort { prefix, min } => {
+                write!(f, "/{} is too short: subnets of a /{} start at /{}", prefix, min, min)
+            }
+        }
+    }
+}
+
+impl Error for CidrError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CidrError::Address(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<IpParseError> for CidrError {
+    fn from(e: IpParseError) -> CidrError {
+        CidrError::Address(e)
+    }
+}
+
+impl Cidr {
+    // Any bits of `address` past the prefix are cleared.
+    pub fn new(address: IpAddr, prefix: u32) -> Result<Cidr, CidrError> {
+        let kind = address.kind();
+        if prefix > kind.bits() {
+            return Err(CidrError::PrefixTooLong { prefix, kind });
+        }
+
+        let network = IpAddr::from_bits(kind, address.to_bits() & mask(kind, prefix));
+        Ok(Cidr {
+            network,
+            prefix: prefix as u8,
+        })
+    }
+
+    pub fn kind(&self) -> IpAddrKind {
+        self.network.kind()
+    }
+
+    pub fn network(&self) -> IpAddr {
+        self.network
+    }
+
+    pub fn prefix(&self) -> u32 {
+        u32::from(self.prefix)
+    }
+
+    // The prefix as an address, e.g. 255.255.255.0 for a /24.
+    pub fn netmask(&self) -> IpAddr {
+        IpAddr::from_bits(self.kind(), mask(self.kind(), self.prefix()))
+    }
+
+    // The last address in the block. IPv6 doesn't have broadcast addresses,
+    // and nor do the V4 blocks too small to spare one (see below).
+    pub fn broadcast(&self) -> Option<IpAddr> {
+        if self.reserves_ends() {
+            Some(self.last())
+        } else {
+            None
+        }
+    }
+
+    // The addresses hosts can use. In a V4 block the first and last are the
+    // network and broadcast addresses, except in a /31 (a point-to-point
+    // link, RFC 3021) or a /32 (a single host), which have none to spare.
+    pub fn first_host(&self) -> IpAddr {
+        if self.reserves_ends() {
+            IpAddr::from_bits(self.kind(), self.network.to_bits() + 1)
+        } else {
+            self.network
+        }
+    }
+
+    pub fn last_host(&self) -> IpAddr {
+        if self.reserves_ends() {
+            IpAddr::from_bits(self.kind(), self.last().to_bits() - 1)
+        } else {
+            self.last()
+        }
+    }
+
+    // How many addresses hosts can use. `::/0` has 2^128, one more than fits
+    // in a u128, so it gives `None`.
+    pub fn host_count(&self) -> Option<u128> {
+        let size = 1u128.checked_shl(self.kind().bits() - self.prefix())?;
+
+        if self.reserves_ends() {
+            Some(size - 2)
+        } else {
+            Some(size)
+        }
+    }
+
+    pub fn contains(&self, address: &IpAddr) -> bool {
+        address.kind() == self.kind()
+            && address.to_bits() & mask(self.kind(), self.prefix()) == self.network.to_bits()
+    }
+
+    // Whether `other` is inside this block (or is this block).
+    pub fn contains_cidr(&self, other: &Cidr) -> bool {
+        other.prefix >= self.prefix && self.contains(&other.network)
+    }
+
+    // The block one bit shorter that this one is half of. `0.0.0.0/0` and
+    // `::/0` are everything, so they don't have one.
+    pub fn supernet(&self) -> Option<Cidr> {
+        match self.prefix() {
+            0 => None,
+            prefix => Cidr::new(self.network, prefix - 1).ok(),
+        }
+    }
+
+    // Splits the block into blocks with a longer prefix: a /24 split at /26
+    // gives four /26s. A /8 split at /32 gives 16 million, so they come one
+    // at a time.
+    pub fn subnets(&self, prefix: u32) -> Result<Subnets, CidrError> {
+        if prefix < self.prefix() {
+            return Err(CidrError::PrefixTooShort {
+                prefix,
+                min: self.prefix,
+            });
+        }
+        let first = Cidr::new(self.network, prefix)?;
+
+        Ok(Subnets {
+            next: Some(first),
+            last: Cidr::new(self.last(), prefix)?,
+        })
+    }
+
+    // The smallest list of blocks covering exactly the same addresses as
+    // `blocks`: blocks inside others are dropped, and neighbouring halves are
+    // merged, so 10.0.0.0/25 and 10.0.0.128/25 become 10.0.0.0/24. The
+    // result is sorted.
+    pub fn aggregate(blocks: &[Cidr]) -> Vec<Cidr> {
+        let mut sorted = blocks.to_vec();
+        sorted.sort();
+
+        let mut merged: Vec<Cidr> = Vec::new();
+        for block in sorted {
+            // Sorted by address, with bigger blocks first, so a block inside
+            // one we already have is inside the last one.
+            if merged.last().is_some_and(|last| last.contains_cidr(&block)) {
+                continue;
+            }
+            merged.push(block);
+
+            // Two halves of the same block make that block, which might in
+            // turn be the other half of the block before it.
+            while merged.len() >= 2 {
+                let (a, b) = (merged[merged.len() - 2], merged[merged.len() - 1]);
+                match a.supernet() {
+                    Some(parent) if a.prefix == b.prefix && b.supernet() == Some(parent) => {
+                        merged.truncate(merged.len() - 2);
+                        merged.push(parent);
+                    }
+                    _ => break,
+                }
+            }
+        }
+
+        merged
+    }
+
+    fn last(&self) -> IpAddr {
+        let host_bits = !mask(self.kind(), self.prefix()) & mask(self.kind(), self.kind().bits());
+        IpAddr::from_bits(self.kind(), self.network.to_bits() | host_bits)
+    }
+
+    fn reserves_ends(&self) -> bool {
+        self.kind() == IpAddrKind::V4 && self.prefix < 31
+    }
+}
+
+// The first `prefix` bits of an address of the given kind, set.
+fn mask(kind: IpAddrKind, prefix: u32) -> u128 {
+    match prefix {
+        0 => 0,
+        prefix => (u128::MAX << (128 - prefix)) >> (128 - kind.bits()),
+    }
+}
+
+// A whole address as a block of one.
+impl From<IpAddr> for Cidr {
+    fn from(address: IpAddr) -> Cidr {
+        Cidr {
+            network: address,
+            prefix: address.kind().bits() as u8,
+        }
+    }
+}
+
+impl fmt::Display for Cidr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.network, self.prefix)
+    }
+}
+
+impl FromStr for Cidr {
+    type Err = CidrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (address, prefix) = s
+            .split_once('/')
+            .ok_or_else(|| CidrError::MissingPrefix(s.to_string()))?;
+
+        let valid = !prefix.is_empty() && prefix.len() <= 3 && prefix.bytes().all(|b| b.is_ascii_digit());
+        if !valid {
+            return Err(CidrError::InvalidPrefix(prefix.to_string()));
+        }
+
+        // Checked against the address kind in `new`.
+        Cidr::new(address.parse()?, prefix.parse().unwrap())
+    }
+}
+
+// The blocks from `Cidr::subnets`, in address order.
+
+#[derive(Debug, Clone)]
+pub struct Subnets {
+    next: Option<Cidr>,
+    last: Cidr,
+}
+
+impl Iterator for Subnets {
+    type Item = Cidr;
+
+    fn next(&mut self) -> Option<Cidr> {
+        let current = self.next?;
+        self.next = if current == self.last {
+            None
+        } else {
+            let size = 1u128 << (current.kind().bits() - current.prefix());
+            let network = IpAddr::from_bits(current.kind(), current.network.to_bits() + size);
+            Some(Cidr {
+                network,
+                prefix: current.prefix,
+            })
+        };
+        Some(current)
+    }
+}
@@ -0,0 +1,199 @@
+use std::fs::File;
+use std::io::{self, BufReader, Write};
+use std::path::PathBuf;
+
+use crate::cidr::Cidr;
+use crate::ip::IpAddr;
+use crate::message;
+use crate::route::{RouteError, RoutingTable};
+
+// The command line: a CIDR calculator, routing table lookups, message
+// scripts, and the chapter's tour.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Cidr(Cidr),
+    Contains(Cidr, Cidr),
+    Split(Cidr, u32),
+    Aggregate(Vec<Cidr>),
+    Route(PathBuf, Vec<IpAddr>),
+    Replay(PathBuf),
+    Tour,
+}
+
+pub const USAGE: &str = "\
+Usage: enums-and-pattern-matching <COMMAND>
+
+Commands:
+  cidr <CIDR>                   network, netmask, broadcast and hosts of a block
+                                such as 10.0.0.0/8 or 2001:db8::/32
+  cidr contains <CIDR> <ADDRESS|CIDR>
+                                whether the block holds an address or block
+  cidr split <CIDR> <PREFIX>    the block's subnets with a longer prefix
+  cidr aggregate <CIDR>...      the fewest blocks covering the same addresses
+  route <FILE> <ADDRESS>...     look addresses up in a file of routes, one per
+                                line as PREFIX via ADDRESS or PREFIX dev NAME
+  replay <FILE>                 run a script of messages (quit, move X Y,
+                                write TEXT, color R G B) and show the result;
+                                - reads the script from standard input
+  tour                          walk through the chapter's examples
+  help                          print this help";
+
+impl Command {
+    // Parses the arguments that follow the program name.
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Command, String> {
+        let mut args = args.into_iter();
+        let name = args.next().ok_or_else(|| String::from(USAGE))?;
+
+        let command = match name.as_str() {
+            "cidr" => cidr(&mut args)?,
+            "route" => route(&mut args)?,
+            "replay" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| String::from("replay needs a script, e.g. replay script.txt"))?;
+                Command::Replay(PathBuf::from(path))
+            }
+            "tour" => Command::Tour,
+            "help" | "-h" | "--help" => return Err(String::from(USAGE)),
+            other => return Err(format!("unknown command '{}'\n\n{}", other, USAGE)),
+        };
+
+        match args.next() {
+            Some(extra) => Err(format!("unexpected argument '{}' after {}", extra, name)),
+            None => Ok(command),
+        }
+    }
+}
+
+fn cidr<I: Iterator<Item = String>>(args: &mut I) -> Result<Command, String> {
+    let first = args
+        .next()
+        .ok_or_else(|| String::from("cidr needs a block, e.g. cidr 10.0.0.0/8"))?;
+
+    match first.as_str() {
+        "contains" => {
+            let block = parse_cidr(args.next().as_deref(), "cidr contains")?;
+            let inner = args
+                .next()
+                .ok_or_else(|| String::from("cidr contains needs an address or block to look for"))?;
+            Ok(Command::Contains(block, parse_address_or_cidr(&inner)?))
+        }
+        "split" => {
+            let block = parse_cidr(args.next().as_deref(), "cidr split")?;
+            let prefix = args
+                .next()
+                .ok_or_else(|| String::from("cidr split needs a prefix length, e.g. cidr split 10.0.0.0/8 16"))?;
+            let prefix = prefix
+                .trim_start_matches('/')
+                .parse()
+                .map_err(|_| format!("'{}' isn't a valid prefix length", prefix))?;
+            block
+                .subnets(prefix)
+                .map_err(|e| format!("can't split {}: {}", block, e))?;
+            Ok(Command::Split(block, prefix))
+        }
+        "aggregate" => {
+            let blocks = args
+                .map(|arg| arg.parse::<Cidr>().map_err(|e| e.to_string()))
+                .collect::<Result<Vec<_>, _>>()?;
+            if blocks.is_empty() {
+                return Err(String::from("cidr aggregate needs at least one block"));
+            }
+            Ok(Command::Aggregate(blocks))
+        }
+        _ => Ok(Command::Cidr(parse_cidr(Some(&first), "cidr")?)),
+    }
+}
+
+fn route<I: Iterator<Item = String>>(args: &mut I) -> Result<Command, String> {
+    let path = args
+        .next()
+        .ok_or_else(|| String::from("route needs a file of routes, e.g. route routes.txt 10.1.2.3"))?;
+    let addresses = args
+        .map(|arg| arg.parse::<IpAddr>().map_err(|e| e.to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+    if addresses.is_empty() {
+        return Err(String::from("route needs at least one address to look up"));
+    }
+    Ok(Command::Route(PathBuf::from(path), addresses))
+}
+
+fn parse_cidr(arg: Option<&str>, command: &str) -> Result<Cidr, String> {
+    let arg = arg.ok_or_else(|| format!("{} needs a block, e.g. 10.0.0.0/8", command))?;
+    arg.parse::<Cidr>().map_err(|e| e.to_string())
+}
+
+// A bare address counts as a block of one.
+pub fn parse_address_or_cidr(text: &str) -> Result<Cidr, String> {
+    if text.contains('/') {
+        text.parse::<Cidr>().map_err(|e| e.to_string())
+    } else {
+        text.parse::<IpAddr>().map(Cidr::from).map_err(|e| e.to_string())
+    }
+}
+
+// Runs everything but `tour`.
+
+pub fn run<W: Write>(command: &Command, output: &mut W) -> io::Result<()> {
+    match command {
+        Command::Cidr(block) => describe(block, output),
+        Command::Contains(block, inner) => {
+            writeln!(output, "Does {} contain {}? {}", block, inner, block.contains_cidr(inner))
+        }
+        Command::Split(block, prefix) => {
+            // `parse` checks the prefix, but a `Command` can be made by hand.
+            let subnets = block.subnets(*prefix).map_err(|e| {
+                io::Error::new(io::ErrorKind::InvalidInput, format!("can't split {}: {}", block, e))
+            })?;
+            for subnet in subnets {
+                writeln!(output, "{}", subnet)?;
+            }
+            Ok(())
+        }
+        Command::Aggregate(blocks) => {
+            for block in Cidr::aggregate(blocks) {
+                writeln!(output, "{}", block)?;
+            }
+            Ok(())
+        }
+        Command::Route(path, addresses) => {
+            let table = RoutingTable::load(path).map_err(|e| match e {
+                RouteError::Io(e) => e,
+                e => io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), e)),
+            })?;
+            for address in addresses {
+                match table.lookup(address) {
+                    Some((prefix, next_hop)) => {
+                        writeln!(output, "{} -> {} (by {})", address, next_hop, prefix)?
+                    }
+                    None => writeln!(output, "{} -> no route", address)?,
+                }
+            }
+            Ok(())
+        }
+        Command::Replay(path) => {
+            let state = if path.as_os_str() == "-" {
+                message::replay(io::stdin().lock(), output)?
+            } else {
+                message::replay(BufReader::new(File::open(path)?), output)?
+            };
+            writeln!(output, "{}", state)
+        }
+        Command::Tour => Ok(()),
+    }
+}
+
+fn describe<W: Write>(block: &Cidr, output: &mut W) -> io::Result<()> {
+    writeln!(output, "Network:    {}", block)?;
+    writeln!(output, "Netmask:    {}", block.netmask())?;
+    if let Some(broadcast) = block.broadcast() {
+        writeln!(output, "Broadcast:  {}", broadcast)?;
+    }
+    writeln!(output, "First host: {}", block.first_host())?;
+    writeln!(output, "Last host:  {}", block.last_host())?;
+    match block.host_count() {
+        Some(count) => writeln!(output, "Hosts:      {}", count),
+        None => writeln!(output, "Hosts:      2^128 (all of them)"),
+    }
+}
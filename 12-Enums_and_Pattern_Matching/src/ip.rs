@@ -0,0 +1,284 @@
+use std::error::Error;
+use std::fmt;
+use std::net;
+use std::str::FromStr;
+
+// The chapter's `IpAddr3`, grown up. A V4 address is still its four octets,
+// but a V6 address is its eight 16-bit segments rather than a `String`, so
+// it can be checked, compared and printed properly.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IpAddrKind {
+    V4,
+    V6,
+}
+
+impl IpAddrKind {
+    // How many bits an address of this kind has.
+    pub fn bits(self) -> u32 {
+        match self {
+            IpAddrKind::V4 => 32,
+            IpAddrKind::V6 => 128,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum IpAddr {
+    V4(u8, u8, u8, u8),
+    V6([u16; 8]),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IpParseError {
+    Empty,
+    InvalidV4(String),
+    InvalidV6(String),
+}
+
+impl fmt::Display for IpParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IpParseError::Empty => write!(f, "an IP address can't be empty"),
+            IpParseError::InvalidV4(s) => write!(f, "'{}' isn't a valid IPv4 address", s),
+            IpParseError::InvalidV6(s) => write!(f, "'{}' isn't a valid IPv6 address", s),
+        }
+    }
+}
+
+impl Error for IpParseError {}
+
+impl IpAddr {
+    pub fn kind(&self) -> IpAddrKind {
+        match self {
+            IpAddr::V4(..) => IpAddrKind::V4,
+            IpAddr::V6(_) => IpAddrKind::V6,
+        }
+    }
+
+    // The address as a number, for V4 in the low 32 bits.
+    pub fn to_bits(&self) -> u128 {
+        match *self {
+            IpAddr::V4(a, b, c, d) => u128::from(u32::from_be_bytes([a, b, c, d])),
+            IpAddr::V6(segments) => segments.iter().fold(0, |bits, &s| bits << 16 | u128::from(s)),
+        }
+    }
+
+    // The other way round. Bits that don't fit the kind are ignored.
+    pub fn from_bits(kind: IpAddrKind, bits: u128) -> IpAddr {
+        match kind {
+            IpAddrKind::V4 => {
+                let [a, b, c, d] = (bits as u32).to_be_bytes();
+                IpAddr::V4(a, b, c, d)
+            }
+            IpAddrKind::V6 => {
+                let mut segments = [0; 8];
+                for (i, segment) in segments.iter_mut().enumerate() {
+                    *segment = (bits >> (16 * (7 - i))) as u16;
+                }
+                IpAddr::V6(segments)
+            }
+        }
+    }
+
+    // `::ffff:a.b.c.d` is how IPv6 writes the IPv4 address a.b.c.d.
+    pub fn to_ipv6_mapped(&self) -> IpAddr {
+        match *self {
+            IpAddr::V4(a, b, c, d) => IpAddr::V6([
+                0,
+                0,
+                0,
+                0,
+                0,
+                0xffff,
+                u16::from_be_bytes([a, b]),
+                u16::from_be_bytes([c, d]),
+            ]),
+            v6 => v6,
+        }
+    }
+
+    // The IPv4 address inside an IPv4-mapped IPv6 address, if it is one.
+    pub fn to_ipv4_mapped(&self) -> Option<IpAddr> {
+        match *self {
+            IpAddr::V6([0, 0, 0, 0, 0, 0xffff, high, low]) => {
+                let [a, b] = high.to_be_bytes();
+                let [c, d] = low.to_be_bytes();
+                Some(IpAddr::V4(a, b, c, d))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl From<net::IpAddr> for IpAddr {
+    fn from(ip: net::IpAddr) -> IpAddr {
+        match ip {
+            net::IpAddr::V4(v4) => {
+                let [a, b, c, d] = v4.octets();
+                IpAddr::V4(a, b, c, d)
+            }
+            net::IpAddr::V6(v6) => IpAddr::V6(v6.segments()),
+        }
+    }
+}
+
+impl From<IpAddr> for net::IpAddr {
+    fn from(ip: IpAddr) -> net::IpAddr {
+        match ip {
+            IpAddr::V4(a, b, c, d) => net::IpAddr::V4(net::Ipv4Addr::new(a, b, c, d)),
+            IpAddr::V6(s) => {
+                net::IpAddr::V6(net::Ipv6Addr::new(s[0], s[1], s[2], s[3], s[4], s[5], s[6], s[7]))
+            }
+        }
+    }
+}
+
+// V4 addresses print as the usual dotted quad. V6 addresses print in the
+// canonical form from RFC 5952: lowercase hex without leading zeros, with
+// the longest run of two or more zero segments (the first, if there's a
+// tie) squashed to `::`. IPv4-mapped addresses end in a dotted quad.
+
+impl fmt::Display for IpAddr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let segments = match *self {
+            IpAddr::V4(a, b, c, d) => return write!(f, "{}.{}.{}.{}", a, b, c, d),
+            IpAddr::V6(segments) => segments,
+        };
+
+        if let Some(v4) = self.to_ipv4_mapped() {
+            return write!(f, "::ffff:{}", v4);
+        }
+
+        // The longest run of zeros, as (start, length).
+        let mut longest = (0, 0);
+        let mut run = (0, 0);
+        for (i, &segment) in segments.iter().enumerate() {
+            if segment != 0 {
+                run = (i + 1, 0);
+                continue;
+            }
+            run.1 += 1;
+            if run.1 > longest.1 {
+                longest = run;
+            }
+        }
+
+        let hex = |segments: &[u16]| {
+            segments
+                .iter()
+                .map(|s| format!("{:x}", s))
+                .collect::<Vec<_>>()
+                .join(":")
+        };
+
+        if longest.1 < 2 {
+            write!(f, "{}", hex(&segments))
+        } else {
+            let (start, length) = longest;
+            write!(
+                f,
+                "{}::{}",
+                hex(&segments[..start]),
+                hex(&segments[start + length..])
+            )
+        }
+    }
+}
+
+// Parses the same forms as `std::net::IpAddr`: dotted quads for V4 (no
+// leading zeros, so `010.0.0.1` isn't mistaken for octal), and for V6 up to
+// eight hex segments with at most one `::`, optionally ending in a dotted
+// quad in place of the last two segments.
+
+impl FromStr for IpAddr {
+    type Err = IpParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(IpParseError::Empty);
+        }
+
+        if s.contains(':') {
+            parse_v6(s).map(IpAddr::V6).ok_or_else(|| IpParseError::InvalidV6(s.to_string()))
+        } else {
+            let [a, b, c, d] = parse_v4(s).ok_or_else(|| IpParseError::InvalidV4(s.to_string()))?;
+            Ok(IpAddr::V4(a, b, c, d))
+        }
+    }
+}
+
+fn parse_v4(s: &str) -> Option<[u8; 4]> {
+    let mut octets = [0; 4];
+    let mut parts = s.split('.');
+
+    for octet in octets.iter_mut() {
+        let part = parts.next()?;
+        let digits_only = !part.is_empty() && part.len() <= 3 && part.bytes().all(|b| b.is_ascii_digit());
+        if !digits_only || (part.len() > 1 && part.starts_with('0')) {
+            return None;
+        }
+        *octet = part.parse().ok()?;
+    }
+
+    match parts.next() {
+        Some(_) => None,
+        None => Some(octets),
+    }
+}
+
+fn parse_v6(s: &str) -> Option<[u16; 8]> {
+    let (head, tail) = match s.find("::") {
+        Some(i) => (&s[..i], Some(&s[i + 2..])),
+        None => (s, None),
+    };
+
+    // Only the end of the whole address can be a dotted quad: the part after
+    // the `::` if there is one, or everything if there isn't.
+    let head = parse_groups(head, tail.is_none())?;
+    let mut segments = [0; 8];
+
+    match tail {
+        None if head.len() == 8 => segments.copy_from_slice(&head),
+        None => return None,
+        Some(tail) => {
+            let tail = parse_groups(tail, true)?;
+            // `::` has to stand for at least one zero segment.
+            if head.len() + tail.len() > 7 {
+                return None;
+            }
+            segments[..head.len()].copy_from_slice(&head);
+            segments[8 - tail.len()..].copy_from_slice(&tail);
+        }
+    }
+
+    Some(segments)
+}
+
+// The colon-separated segments either side of a `::`. If `ends_address`,
+// the last one may be a dotted quad, which counts as two segments.
+fn parse_groups(s: &str, ends_address: bool) -> Option<Vec<u16>> {
+    if s.is_empty() {
+        return Some(Vec::new());
+    }
+
+    let parts: Vec<&str> = s.split(':').collect();
+    let mut segments = Vec::new();
+
+    for (i, part) in parts.iter().enumerate() {
+        if ends_address && i == parts.len() - 1 && part.contains('.') {
+            let [a, b, c, d] = parse_v4(part)?;
+            segments.push(u16::from_be_bytes([a, b]));
+            segments.push(u16::from_be_bytes([c, d]));
+        } else if !part.is_empty() && part.len() <= 4 && part.bytes().all(|b| b.is_ascii_hexdigit()) {
+            segments.push(u16::from_str_radix(part, 16).ok()?);
+        } else {
+            return None;
+        }
+    }
+
+    if segments.len() > 8 {
+        return None;
+    }
+    Some(segments)
+}
@@ -0,0 +1,10 @@
+// Chapter 6, enums and pattern matching. The enums that grew past the
+// chapter's examples live in this library so they can be tested; main.rs
+// walks through the examples themselves.
+
+pub mod cidr;
+pub mod cli;
+pub mod ip;
+pub mod message;
+pub mod route;
+pub mod wire;
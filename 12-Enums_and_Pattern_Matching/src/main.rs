@@ -6,7 +6,40 @@
 
 // * enum values can only be one of its variants.
 
+// Many of the examples below are defined only to show what they look like,
+// and never used.
+#![allow(dead_code, unused_variables)]
+
+use std::io;
+use std::process;
+
+use enums_and_pattern_matching::cli::{self, Command};
+use enums_and_pattern_matching::ip;
+
 fn main() {
+    let command = match Command::parse(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("{}", message);
+            process::exit(2);
+        }
+    };
+
+    let result = match command {
+        Command::Tour => {
+            tour();
+            Ok(())
+        }
+        command => cli::run(&command, &mut io::stdout()),
+    };
+
+    if let Err(e) = result {
+        eprintln!("enums-and-pattern-matching: {}", e);
+        process::exit(1);
+    }
+}
+
+fn tour() {
     
     enum IpAddrKind {
         V4,
@@ -26,6 +59,9 @@ fn main() {
     // We can then, for instance, define a function that takes any IpAddrKind:
     fn route(ip_kind: IpAddrKind) {}
 
+    // (src/route.rs has one that does some routing: a table that keeps V4 and
+    // V6 routes apart by kind, and finds the best route to an address.)
+
     // And we can call this function with either variant:
     route(IpAddrKind::V4);
     route(IpAddrKind::V6);
@@ -82,6 +118,15 @@ fn main() {
 
     let loopback = IpAddr3::V6(String::from("::1"));
 
+    // With eight 16-bit numbers in place of the V6 String, `ip::IpAddr` can
+    // parse addresses, check them, and print them back in their shortest form:
+
+    let home: ip::IpAddr = "127.0.0.1".parse().unwrap();
+    let loopback: ip::IpAddr = "0:0:0:0:0:0:0:1".parse().unwrap();
+    let mapped = home.to_ipv6_mapped();
+
+    println!("{} and {}, or {} in IPv6", home, loopback, mapped);
+
 
     // Let’s look at how the standard library defines IpAddr: 
     
@@ -144,6 +189,10 @@ fn main() {
     let m = Message::Write(String::from("hello"));
 
     m.call();
+
+    // src/message.rs puts these messages to work, as a protocol for moving a
+    // cursor around and writing text: each variant is matched to something
+    // to do.
 }
 
 
@@ -0,0 +1,234 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+// The chapter's `Message` enum as a little command protocol. Each message is
+// one thing to do to a `State`, written on a line of its own:
+//
+//     move 3 4
+//     color 255 0 0
+//     write hello
+//     quit
+//
+// `dispatch` matches on the message and calls the right `Handler` method,
+// checking it's a sensible thing to do first.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    Quit,
+    Move { x: i32, y: i32 },
+    Write(String),
+    ChangeColor(i32, i32, i32),
+}
+
+// Anything messages can be applied to. `dispatch` only calls these with
+// colors that are in range, and never once `is_running` says false.
+pub trait Handler {
+    fn is_running(&self) -> bool;
+    fn quit(&mut self);
+    fn move_to(&mut self, x: i32, y: i32);
+    fn write(&mut self, text: &str);
+    fn change_color(&mut self, r: u8, g: u8, b: u8);
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DispatchError {
+    Stopped,
+    ColorOutOfRange(i32),
+}
+
+impl fmt::Display for DispatchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DispatchError::Stopped => write!(f, "already quit"),
+            DispatchError::ColorOutOfRange(n) => {
+                write!(f, "{} is out of range (colors go from 0 to 255)", n)
+            }
+        }
+    }
+}
+
+impl Error for DispatchError {}
+
+pub fn dispatch<H: Handler + ?Sized>(handler: &mut H, message: &Message) -> Result<(), DispatchError> {
+    if !handler.is_running() {
+        return Err(DispatchError::Stopped);
+    }
+
+    match message {
+        Message::Quit => handler.quit(),
+        Message::Move { x, y } => handler.move_to(*x, *y),
+        Message::Write(text) => handler.write(text),
+        Message::ChangeColor(r, g, b) => {
+            let channel = |c: i32| u8::try_from(c).map_err(|_| DispatchError::ColorOutOfRange(c));
+            handler.change_color(channel(*r)?, channel(*g)?, channel(*b)?);
+        }
+    }
+    Ok(())
+}
+
+// Where the cursor is, what's been written, the color to write in, and
+// whether it's still taking messages.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct State {
+    pub cursor: (i32, i32),
+    pub text: String,
+    pub color: (u8, u8, u8),
+    pub running: bool,
+}
+
+impl Default for State {
+    fn default() -> State {
+        State {
+            cursor: (0, 0),
+            text: String::new(),
+            color: (0, 0, 0),
+            running: true,
+        }
+    }
+}
+
+impl State {
+    pub fn new() -> State {
+        State::default()
+    }
+}
+
+impl Handler for State {
+    fn is_running(&self) -> bool {
+        self.running
+    }
+
+    fn quit(&mut self) {
+        self.running = false;
+    }
+
+    fn move_to(&mut self, x: i32, y: i32) {
+        self.cursor = (x, y);
+    }
+
+    fn write(&mut self, text: &str) {
+        self.text.push_str(text);
+    }
+
+    fn change_color(&mut self, r: u8, g: u8, b: u8) {
+        self.color = (r, g, b);
+    }
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (r, g, b) = self.color;
+        writeln!(f, "cursor:  ({}, {})", self.cursor.0, self.cursor.1)?;
+        writeln!(f, "color:   {} {} {}", r, g, b)?;
+        writeln!(f, "text:    {:?}", self.text)?;
+        write!(f, "running: {}", self.running)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    Empty,
+    UnknownCommand(String),
+    Arguments(&'static str),
+    Number(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "there's no message"),
+            ParseError::UnknownCommand(s) => write!(
+                f,
+                "unknown message '{}' (expected quit, move, write or color)",
+                s
+            ),
+            ParseError::Arguments(usage) => write!(f, "expected {}", usage),
+            ParseError::Number(s) => write!(f, "'{}' isn't a whole number", s),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+// Writes the message the way `from_str` reads it.
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Message::Quit => write!(f, "quit"),
+            Message::Move { x, y } => write!(f, "move {} {}", x, y),
+            Message::Write(text) => write!(f, "write {}", text),
+            Message::ChangeColor(r, g, b) => write!(f, "color {} {} {}", r, g, b),
+        }
+    }
+}
+
+// Everything after `write ` is the text, spaces and all.
+impl FromStr for Message {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let line = s.trim_start().trim_end_matches(['\r', '\n']);
+        let (command, rest) = line.split_once(' ').unwrap_or((line, ""));
+
+        let numbers = |usage: &'static str, count: usize| -> Result<Vec<i32>, ParseError> {
+            let words: Vec<&str> = rest.split_whitespace().collect();
+            if words.len() != count {
+                return Err(ParseError::Arguments(usage));
+            }
+            words
+                .iter()
+                .map(|word| word.parse().map_err(|_| ParseError::Number(word.to_string())))
+                .collect()
+        };
+
+        match command {
+            "" => Err(ParseError::Empty),
+            "quit" if rest.trim().is_empty() => Ok(Message::Quit),
+            "quit" => Err(ParseError::Arguments("quit on its own")),
+            "move" => {
+                let n = numbers("move X Y", 2)?;
+                Ok(Message::Move { x: n[0], y: n[1] })
+            }
+            "write" => Ok(Message::Write(rest.to_string())),
+            "color" => {
+                let n = numbers("color R G B", 3)?;
+                Ok(Message::ChangeColor(n[0], n[1], n[2]))
+            }
+            other => Err(ParseError::UnknownCommand(other.to_string())),
+        }
+    }
+}
+
+// Runs a script of messages, one per line, against a new `State` and gives
+// back where it ended up. Blank lines and lines starting with `#` are
+// skipped. A line that doesn't parse or can't be applied is reported to
+// `output`, and the script carries on; `quit` ends it.
+
+pub fn replay<I: BufRead, O: Write>(input: I, output: &mut O) -> io::Result<State> {
+    let mut state = State::new();
+
+    for (i, line) in input.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+
+        let result = line
+            .parse::<Message>()
+            .map_err(|e| e.to_string())
+            .and_then(|message| dispatch(&mut state, &message).map_err(|e| e.to_string()));
+        if let Err(message) = result {
+            writeln!(output, "line {}: {}", i + 1, message)?;
+        }
+
+        if !state.running {
+            break;
+        }
+    }
+
+    Ok(state)
+}
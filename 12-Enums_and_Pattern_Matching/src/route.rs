@@ -0,0 +1,342 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::cidr::{Cidr, CidrError};
+use crate::ip::{IpAddr, IpAddrKind, IpParseError};
+
+// The chapter's `fn route(ip_kind: IpAddrKind) {}`, doing what a router does:
+// given an address, find the most specific route to it. A table holding
+// 10.0.0.0/8 and 10.1.0.0/16 sends 10.1.2.3 by the /16 and 10.2.3.4 by the
+// /8. That's the longest prefix match.
+//
+// Each kind of address gets its own binary trie, one level per bit, with a
+// route hung on the node at the end of its prefix. A lookup walks down the
+// address's bits and remembers the last route it passed, so it costs at most
+// 32 or 128 steps however many routes there are.
+
+// Where to send a packet: to a gateway, or straight out of an interface.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NextHop {
+    Gateway(IpAddr),
+    Interface(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    pub prefix: Cidr,
+    pub next_hop: NextHop,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RoutingTable {
+    v4: Trie,
+    v6: Trie,
+}
+
+// The nodes live in one Vec and point at each other by index.
+#[derive(Debug, Clone)]
+struct Trie {
+    nodes: Vec<Node>,
+    routes: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Node {
+    children: [Option<usize>; 2],
+    next_hop: Option<NextHop>,
+}
+
+impl Default for Trie {
+    fn default() -> Trie {
+        Trie {
+            nodes: vec![Node::default()],
+            routes: 0,
+        }
+    }
+}
+
+// Bit `i` of an address, counting from the most significant.
+fn bit(kind: IpAddrKind, bits: u128, i: u32) -> usize {
+    (bits >> (kind.bits() - 1 - i) & 1) as usize
+}
+
+impl RoutingTable {
+    pub fn new() -> RoutingTable {
+        RoutingTable::default()
+    }
+
+    // Adds a route, giving back the next hop it replaced, if the prefix was
+    // already in the table.
+    pub fn insert(&mut self, prefix: Cidr, next_hop: NextHop) -> Option<NextHop> {
+        let kind = prefix.kind();
+        let bits = prefix.network().to_bits();
+        let trie = self.trie_mut(kind);
+
+        let mut node = 0;
+        for i in 0..prefix.prefix() {
+            let b = bit(kind, bits, i);
+            node = match trie.nodes[node].children[b] {
+                Some(child) => child,
+                None => {
+                    trie.nodes.push(Node::default());
+                    let child = trie.nodes.len() - 1;
+                    trie.nodes[node].children[b] = Some(child);
+                    child
+                }
+            };
+        }
+
+        let replaced = trie.nodes[node].next_hop.replace(next_hop);
+        if replaced.is_none() {
+            trie.routes += 1;
+        }
+        replaced
+    }
+
+    // Takes a route out, giving back its next hop. The trie keeps the empty
+    // nodes on the way to it; they're reused if the prefix comes back.
+    pub fn remove(&mut self, prefix: &Cidr) -> Option<NextHop> {
+        let trie = self.trie_mut(prefix.kind());
+        let node = trie.find(prefix)?;
+
+        let removed = trie.nodes[node].next_hop.take();
+        if removed.is_some() {
+            trie.routes -= 1;
+        }
+        removed
+    }
+
+    // The next hop for exactly this prefix, without any matching.
+    pub fn get(&self, prefix: &Cidr) -> Option<&NextHop> {
+        let trie = self.trie(prefix.kind());
+        trie.nodes[trie.find(prefix)?].next_hop.as_ref()
+    }
+
+    // The most specific route to `address`, if any route covers it.
+    pub fn lookup(&self, address: &IpAddr) -> Option<(Cidr, &NextHop)> {
+        let kind = address.kind();
+        let bits = address.to_bits();
+        let trie = self.trie(kind);
+
+        let mut node = &trie.nodes[0];
+        let mut best = node.next_hop.as_ref().map(|hop| (0, hop));
+        for i in 0..kind.bits() {
+            node = match node.children[bit(kind, bits, i)] {
+                Some(child) => &trie.nodes[child],
+                None => break,
+            };
+            if let Some(hop) = &node.next_hop {
+                best = Some((i + 1, hop));
+            }
+        }
+
+        // The prefix lengths are all in range, so `new` can't fail.
+        best.map(|(length, hop)| (Cidr::new(*address, length).unwrap(), hop))
+    }
+
+    pub fn len(&self) -> usize {
+        self.v4.routes + self.v6.routes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Every route, V4 first, in address order with shorter prefixes first.
+    pub fn routes(&self) -> Vec<Route> {
+        let mut routes = Vec::with_capacity(self.len());
+        for &(kind, trie) in [(IpAddrKind::V4, &self.v4), (IpAddrKind::V6, &self.v6)].iter() {
+            trie.collect(kind, 0, 0, 0, &mut routes);
+        }
+        routes
+    }
+
+    // Reads routes a line at a time, one per line as `Route` parses them.
+    // Blank lines and anything after a `#` are ignored. A prefix listed twice
+    // takes its last next hop.
+    pub fn read<R: BufRead>(reader: R) -> Result<RoutingTable, RouteError> {
+        let mut table = RoutingTable::new();
+
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            let text = match line.find('#') {
+                Some(comment) => &line[..comment],
+                None => &line,
+            };
+            if text.trim().is_empty() {
+                continue;
+            }
+
+            let route: Route = text.parse().map_err(|error| RouteError::Line {
+                line: i + 1,
+                error,
+            })?;
+            table.insert(route.prefix, route.next_hop);
+        }
+
+        Ok(table)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<RoutingTable, RouteError> {
+        RoutingTable::read(BufReader::new(File::open(path)?))
+    }
+
+    fn trie(&self, kind: IpAddrKind) -> &Trie {
+        match kind {
+            IpAddrKind::V4 => &self.v4,
+            IpAddrKind::V6 => &self.v6,
+        }
+    }
+
+    fn trie_mut(&mut self, kind: IpAddrKind) -> &mut Trie {
+        match kind {
+            IpAddrKind::V4 => &mut self.v4,
+            IpAddrKind::V6 => &mut self.v6,
+        }
+    }
+}
+
+impl Trie {
+    // The node at the end of `prefix`, if the trie goes that far.
+    fn find(&self, prefix: &Cidr) -> Option<usize> {
+        let kind = prefix.kind();
+        let bits = prefix.network().to_bits();
+
+        let mut node = 0;
+        for i in 0..prefix.prefix() {
+            node = self.nodes[node].children[bit(kind, bits, i)]?;
+        }
+        Some(node)
+    }
+
+    fn collect(&self, kind: IpAddrKind, node: usize, bits: u128, depth: u32, routes: &mut Vec<Route>) {
+        if let Some(hop) = &self.nodes[node].next_hop {
+            routes.push(Route {
+                prefix: Cidr::new(IpAddr::from_bits(kind, bits), depth).unwrap(),
+                next_hop: hop.clone(),
+            });
+        }
+
+        for (b, child) in self.nodes[node].children.iter().enumerate() {
+            if let Some(child) = *child {
+                let bits = bits | (b as u128) << (kind.bits() - 1 - depth);
+                self.collect(kind, child, bits, depth + 1, routes);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RouteParseError {
+    Syntax(String),
+    Prefix(CidrError),
+    Gateway(IpParseError),
+}
+
+impl fmt::Display for RouteParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RouteParseError::Syntax(s) => write!(
+                f,
+                "'{}' isn't a route (expected PREFIX via ADDRESS or PREFIX dev INTERFACE)",
+                s
+            ),
+            RouteParseError::Prefix(e) => write!(f, "bad prefix: {}", e),
+            RouteParseError::Gateway(e) => write!(f, "bad gateway: {}", e),
+        }
+    }
+}
+
+impl Error for RouteParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RouteParseError::Syntax(_) => None,
+            RouteParseError::Prefix(e) => Some(e),
+            RouteParseError::Gateway(e) => Some(e),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum RouteError {
+    Io(io::Error),
+    Line { line: usize, error: RouteParseError },
+}
+
+impl fmt::Display for RouteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RouteError::Io(e) => write!(f, "couldn't read the routes: {}", e),
+            RouteError::Line { line, error } => write!(f, "line {}: {}", line, error),
+        }
+    }
+}
+
+impl Error for RouteError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RouteError::Io(e) => Some(e),
+            RouteError::Line { error, .. } => Some(error),
+        }
+    }
+}
+
+impl From<io::Error> for RouteError {
+    fn from(e: io::Error) -> RouteError {
+        RouteError::Io(e)
+    }
+}
+
+impl fmt::Display for NextHop {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NextHop::Gateway(gateway) => write!(f, "via {}", gateway),
+            NextHop::Interface(name) => write!(f, "dev {}", name),
+        }
+    }
+}
+
+// Routes are written the way `ip route` shows them:
+//
+//     10.0.0.0/8 via 192.168.1.1
+//     192.168.1.0/24 dev eth0
+//     default via 192.168.1.254
+//
+// `default` is 0.0.0.0/0, or ::/0 if the gateway is a V6 address.
+
+impl fmt::Display for Route {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.prefix, self.next_hop)
+    }
+}
+
+impl FromStr for Route {
+    type Err = RouteParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        let (prefix, next_hop) = match words.as_slice() {
+            [prefix, "via", gateway] => (
+                *prefix,
+                NextHop::Gateway(gateway.parse().map_err(RouteParseError::Gateway)?),
+            ),
+            [prefix, "dev", name] => (*prefix, NextHop::Interface(name.to_string())),
+            _ => return Err(RouteParseError::Syntax(s.trim().to_string())),
+        };
+
+        let prefix = match (prefix, &next_hop) {
+            ("default", NextHop::Gateway(IpAddr::V6(_))) => "::/0".parse(),
+            ("default", _) => "0.0.0.0/0".parse(),
+            (prefix, _) => prefix.parse(),
+        };
+
+        Ok(Route {
+            prefix: prefix.map_err(RouteParseError::Prefix)?,
+            next_hop,
+        })
+    }
+}
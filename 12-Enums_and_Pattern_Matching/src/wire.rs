@@ -0,0 +1,214 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::string::FromUtf8Error;
+
+use crate::message::Message;
+
+// `Message`s as bytes, for sending between processes. Each message is
+//
+//     version  u8     always VERSION for now
+//     tag      u8     which variant
+//     payload         depends on the variant
+//
+// and numbers are big-endian:
+//
+//     0  Quit          nothing
+//     1  Move          x: i32, y: i32
+//     2  Write         length: u32, then that many bytes of UTF-8
+//     3  ChangeColor   r: i32, g: i32, b: i32
+//
+// A stream of messages is just one after another; each one says how long it
+// is, so nothing else is needed to tell where the next one starts.
+
+pub const VERSION: u8 = 1;
+
+const QUIT: u8 = 0;
+const MOVE: u8 = 1;
+const WRITE: u8 = 2;
+const CHANGE_COLOR: u8 = 3;
+
+#[derive(Debug)]
+pub enum DecodeError {
+    Io(io::Error),
+    Truncated,
+    UnknownVersion(u8),
+    UnknownTag(u8),
+    InvalidUtf8(FromUtf8Error),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::Io(e) => write!(f, "couldn't read the message: {}", e),
+            DecodeError::Truncated => write!(f, "the message stops part way through"),
+            DecodeError::UnknownVersion(v) => {
+                write!(f, "version {} messages aren't supported (only version {})", v, VERSION)
+            }
+            DecodeError::UnknownTag(tag) => write!(f, "there's no message with tag {}", tag),
+            DecodeError::InvalidUtf8(e) => write!(f, "the text isn't UTF-8: {}", e),
+        }
+    }
+}
+
+impl Error for DecodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DecodeError::Io(e) => Some(e),
+            DecodeError::InvalidUtf8(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+// Running out of bytes part way through is a truncated message, not an I/O
+// problem.
+impl From<io::Error> for DecodeError {
+    fn from(e: io::Error) -> DecodeError {
+        match e.kind() {
+            io::ErrorKind::UnexpectedEof => DecodeError::Truncated,
+            _ => DecodeError::Io(e),
+        }
+    }
+}
+
+// Panics if a `Write` holds more than u32::MAX bytes of text, which is
+// 4GiB: far more than anyone should be sending in one message.
+pub fn encode(message: &Message) -> Vec<u8> {
+    let mut bytes = vec![VERSION];
+
+    match message {
+        Message::Quit => bytes.push(QUIT),
+        Message::Move { x, y } => {
+            bytes.push(MOVE);
+            bytes.extend_from_slice(&x.to_be_bytes());
+            bytes.extend_from_slice(&y.to_be_bytes());
+        }
+        Message::Write(text) => {
+            let length = u32::try_from(text.len()).expect("text too long to encode");
+            bytes.push(WRITE);
+            bytes.extend_from_slice(&length.to_be_bytes());
+            bytes.extend_from_slice(text.as_bytes());
+        }
+        Message::ChangeColor(r, g, b) => {
+            bytes.push(CHANGE_COLOR);
+            for channel in [r, g, b].iter() {
+                bytes.extend_from_slice(&channel.to_be_bytes());
+            }
+        }
+    }
+
+    bytes
+}
+
+pub fn write_message<W: Write>(writer: &mut W, message: &Message) -> io::Result<()> {
+    writer.write_all(&encode(message))
+}
+
+// The message at the start of `bytes`, and how many bytes it took up.
+pub fn decode(bytes: &[u8]) -> Result<(Message, usize), DecodeError> {
+    let mut rest = bytes;
+    match read_message(&mut rest)? {
+        Some(message) => Ok((message, bytes.len() - rest.len())),
+        None => Err(DecodeError::Truncated),
+    }
+}
+
+// The next message from `reader`, or `None` if it ends cleanly before one
+// starts.
+pub fn read_message<R: Read>(reader: &mut R) -> Result<Option<Message>, DecodeError> {
+    let version = match read_first_byte(reader)? {
+        Some(version) => version,
+        None => return Ok(None),
+    };
+    if version != VERSION {
+        return Err(DecodeError::UnknownVersion(version));
+    }
+
+    let message = match read_array::<_, 1>(reader)?[0] {
+        QUIT => Message::Quit,
+        MOVE => Message::Move {
+            x: read_i32(reader)?,
+            y: read_i32(reader)?,
+        },
+        WRITE => {
+            let length = u32::from_be_bytes(read_array(reader)?);
+            // Read what's there rather than trusting the length enough to
+            // allocate it all up front.
+            let mut text = Vec::new();
+            reader.take(u64::from(length)).read_to_end(&mut text)?;
+            if text.len() as u64 != u64::from(length) {
+                return Err(DecodeError::Truncated);
+            }
+            Message::Write(String::from_utf8(text).map_err(DecodeError::InvalidUtf8)?)
+        }
+        CHANGE_COLOR => Message::ChangeColor(read_i32(reader)?, read_i32(reader)?, read_i32(reader)?),
+        tag => return Err(DecodeError::UnknownTag(tag)),
+    };
+
+    Ok(Some(message))
+}
+
+fn read_first_byte<R: Read>(reader: &mut R) -> Result<Option<u8>, DecodeError> {
+    let mut byte = [0];
+    loop {
+        return match reader.read(&mut byte) {
+            Ok(0) => Ok(None),
+            Ok(_) => Ok(Some(byte[0])),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => Err(DecodeError::Io(e)),
+        };
+    }
+}
+
+fn read_array<R: Read, const N: usize>(reader: &mut R) -> Result<[u8; N], DecodeError> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn read_i32<R: Read>(reader: &mut R) -> Result<i32, DecodeError> {
+    Ok(i32::from_be_bytes(read_array(reader)?))
+}
+
+// Messages from a stream, one at a time, until it ends. Reading is a byte or
+// a few at a time, so wrap slow readers like files and sockets in a
+// `BufReader`. After an error the stream's position is somewhere inside a
+// bad message, so the iterator stops there.
+
+pub struct MessageReader<R> {
+    reader: R,
+    failed: bool,
+}
+
+impl<R: Read> MessageReader<R> {
+    pub fn new(reader: R) -> MessageReader<R> {
+        MessageReader {
+            reader,
+            failed: false,
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: Read> Iterator for MessageReader<R> {
+    type Item = Result<Message, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        match read_message(&mut self.reader) {
+            Ok(message) => message.map(Ok),
+            Err(e) => {
+                self.failed = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use enums_and_pattern_matching::cidr::{Cidr, CidrError};
+use enums_and_pattern_matching::ip::{IpAddr, IpAddrKind, IpParseError};
+
+fn cidr(s: &str) -> Cidr {
+    s.parse().unwrap()
+}
+
+fn ip(s: &str) -> IpAddr {
+    s.parse().unwrap()
+}
+
+#[test]
+fn parses_and_clears_host_bits() {
+    let block = cidr("10.1.2.3/8");
+
+    assert_eq!(block.network(), IpAddr::V4(10, 0, 0, 0));
+    assert_eq!(block.prefix(), 8);
+    assert_eq!(block, cidr("10.0.0.0/8"));
+    assert_eq!(block.to_string(), "10.0.0.0/8");
+    assert_eq!(cidr("2001:db8:1234::1/32").to_string(), "2001:db8::/32");
+    assert_eq!(cidr("0.0.0.0/0").to_string(), "0.0.0.0/0");
+}
+
+#[test]
+fn rejects_malformed_blocks() {
+    assert_eq!(
+        "10.0.0.0".parse::<Cidr>(),
+        Err(CidrError::MissingPrefix(String::from("10.0.0.0")))
+    );
+    assert_eq!("10.0.0.0/".parse::<Cidr>(), Err(CidrError::InvalidPrefix(String::new())));
+    assert_eq!("10.0.0.0/+8".parse::<Cidr>(), Err(CidrError::InvalidPrefix(String::from("+8"))));
+    assert_eq!("10.0.0.0/8/8".parse::<Cidr>(), Err(CidrError::InvalidPrefix(String::from("8/8"))));
+    assert_eq!(
+        "10.0.0.0/33".parse::<Cidr>(),
+        Err(CidrError::PrefixTooLong {
+            prefix: 33,
+            kind: IpAddrKind::V4
+        })
+    );
+    assert_eq!(
+        "::/129".parse::<Cidr>(),
+        Err(CidrError::PrefixTooLong {
+            prefix: 129,
+            kind: IpAddrKind::V6
+        })
+    );
+    assert_eq!(
+        "10.0.0/8".parse::<Cidr>(),
+        Err(CidrError::Address(IpParseError::InvalidV4(String::from("10.0.0"))))
+    );
+}
+
+#[test]
+fn describes_a_v4_block() {
+    let block = cidr("192.168.1.77/24");
+
+    assert_eq!(block.netmask(), ip("255.255.255.0"));
+    assert_eq!(block.broadcast(), Some(ip("192.168.1.255")));
+    assert_eq!(block.first_host(), ip("192.168.1.1"));
+    assert_eq!(block.last_host(), ip("192.168.1.254"));
+    assert_eq!(block.host_count(), Some(254));
+
+    assert_eq!(cidr("0.0.0.0/0").host_count(), Some((1 << 32) - 2));
+    assert_eq!(cidr("172.16.0.0/12").netmask(), ip("255.240.0.0"));
+}
+
+#[test]
+fn small_v4_blocks_use_every_address() {
+    let link = cidr("10.0.0.0/31");
+    assert_eq!(link.broadcast(), None);
+    assert_eq!((link.first_host(), link.last_host()), (ip("10.0.0.0"), ip("10.0.0.1")));
+    assert_eq!(link.host_count(), Some(2));
+
+    let host = cidr("10.0.0.7/32");
+    assert_eq!(host.broadcast(), None);
+    assert_eq!((host.first_host(), host.last_host()), (ip("10.0.0.7"), ip("10.0.0.7")));
+    assert_eq!(host.host_count(), Some(1));
+}
+
+#[test]
+fn describes_a_v6_block() {
+    let block = cidr("2001:db8::/32");
+
+    assert_eq!(block.netmask(), ip("ffff:ffff::"));
+    assert_eq!(block.broadcast(), None);
+    assert_eq!(block.first_host(), ip("2001:db8::"));
+    assert_eq!(block.last_host(), ip("2001:db8:ffff:ffff:ffff:ffff:ffff:ffff"));
+    assert_eq!(block.host_count(), Some(1 << 96));
+    assert_eq!(cidr("::/0").host_count(), None);
+    assert_eq!(cidr("::1/128").host_count(), Some(1));
+}
+
+#[test]
+fn contains_addresses_and_blocks() {
+    let block = cidr("10.0.0.0/8");
+
+    assert!(block.contains(&ip("10.255.0.1")));
+    assert!(!block.contains(&ip("11.0.0.0")));
+    // A V4-mapped address is still a V6 address.
+    assert!(!block.contains(&ip("::ffff:10.0.0.1")));
+    assert!(cidr("0.0.0.0/0").contains(&ip("255.255.255.255")));
+
+    assert!(block.contains_cidr(&cidr("10.20.0.0/16")));
+    assert!(block.contains_cidr(&block));
+    assert!(!cidr("10.20.0.0/16").contains_cidr(&block));
+    assert!(!block.contains_cidr(&cidr("::/0")));
+    assert!(block.contains_cidr(&Cidr::from(ip("10.1.1.1"))));
+}
+
+#[test]
+fn splits_into_subnets() {
+    let block = cidr("10.0.0.0/24");
+
+    let subnets: Vec<String> = block.subnets(26).unwrap().map(|c| c.to_string()).collect();
+    assert_eq!(subnets, ["10.0.0.0/26", "10.0.0.64/26", "10.0.0.128/26", "10.0.0.192/26"]);
+    assert_eq!(block.subnets(24).unwrap().collect::<Vec<_>>(), [block]);
+    assert_eq!(block.subnets(32).unwrap().count(), 256);
+    assert_eq!(
+        block.subnets(16).unwrap_err(),
+        CidrError::PrefixTooShort { prefix: 16, min: 24 }
+    );
+    assert!(block.subnets(33).is_err());
+
+    // Lazily, so huge splits are fine to start on.
+    let mut everything = cidr("::/0").subnets(128).unwrap();
+    assert_eq!(everything.nth(1), Some(cidr("::1/128")));
+    let halves: Vec<Cidr> = cidr("::/0").subnets(1).unwrap().collect();
+    assert_eq!(halves, [cidr("::/1"), cidr("8000::/1")]);
+}
+
+#[test]
+fn finds_supernets() {
+    assert_eq!(cidr("10.0.1.0/24").supernet(), Some(cidr("10.0.0.0/23")));
+    assert_eq!(cidr("0.0.0.0/1").supernet(), Some(cidr("0.0.0.0/0")));
+    assert_eq!(cidr("0.0.0.0/0").supernet(), None);
+}
+
+#[test]
+fn aggregates_blocks() {
+    let aggregate = |blocks: &[&str]| {
+        let blocks: Vec<Cidr> = blocks.iter().map(|s| cidr(s)).collect();
+        Cidr::aggregate(&blocks)
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(aggregate(&["10.0.0.128/25", "10.0.0.0/25"]), ["10.0.0.0/24"]);
+    // Merges cascade: four /26s make a /24, then another /24 makes a /23.
+    assert_eq!(
+        aggregate(&[
+            "10.0.0.0/26",
+            "10.0.0.64/26",
+            "10.0.0.128/26",
+            "10.0.0.192/26",
+            "10.0.1.0/24"
+        ]),
+        ["10.0.0.0/23"]
+    );
+    // Blocks inside others, and duplicates, disappear.
+    assert_eq!(
+        aggregate(&["10.0.0.0/8", "10.1.0.0/16", "10.0.0.0/8", "10.2.3.4/32"]),
+        ["10.0.0.0/8"]
+    );
+    // Neighbours that aren't halves of the same block stay apart.
+    assert_eq!(aggregate(&["10.0.1.0/24", "10.0.2.0/24"]), ["10.0.1.0/24", "10.0.2.0/24"]);
+    assert_eq!(
+        aggregate(&["2001:db8::/33", "192.168.0.0/24", "2001:db8:8000::/33"]),
+        ["192.168.0.0/24", "2001:db8::/32"]
+    );
+    assert_eq!(aggregate(&[]), Vec::<String>::new());
+}
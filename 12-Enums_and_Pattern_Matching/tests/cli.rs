@@ -0,0 +1,157 @@
+use std::path::PathBuf;
+
+use enums_and_pattern_matching::cidr::Cidr;
+use enums_and_pattern_matching::cli::{self, Command};
+use enums_and_pattern_matching::ip::IpAddr;
+
+fn parse(args: &[&str]) -> Result<Command, String> {
+    Command::parse(args.iter().map(|s| s.to_string()))
+}
+
+fn run(args: &[&str]) -> String {
+    let mut output = Vec::new();
+    cli::run(&parse(args).unwrap(), &mut output).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+fn cidr(s: &str) -> Cidr {
+    s.parse().unwrap()
+}
+
+#[test]
+fn commands() {
+    assert_eq!(parse(&["cidr", "10.0.0.0/8"]), Ok(Command::Cidr(cidr("10.0.0.0/8"))));
+    assert_eq!(
+        parse(&["cidr", "contains", "10.0.0.0/8", "10.1.2.3"]),
+        Ok(Command::Contains(cidr("10.0.0.0/8"), cidr("10.1.2.3/32")))
+    );
+    assert_eq!(
+        parse(&["cidr", "split", "10.0.0.0/8", "/16"]),
+        Ok(Command::Split(cidr("10.0.0.0/8"), 16))
+    );
+    assert_eq!(
+        parse(&["cidr", "aggregate", "10.0.0.0/25", "10.0.0.128/25"]),
+        Ok(Command::Aggregate(vec![cidr("10.0.0.0/25"), cidr("10.0.0.128/25")]))
+    );
+    assert_eq!(
+        parse(&["route", "routes.txt", "10.1.2.3", "::1"]),
+        Ok(Command::Route(
+            PathBuf::from("routes.txt"),
+            vec![IpAddr::V4(10, 1, 2, 3), IpAddr::V6([0, 0, 0, 0, 0, 0, 0, 1])]
+        ))
+    );
+    assert_eq!(parse(&["replay", "-"]), Ok(Command::Replay(PathBuf::from("-"))));
+    assert_eq!(parse(&["tour"]), Ok(Command::Tour));
+}
+
+#[test]
+fn mistakes() {
+    assert!(parse(&[]).unwrap_err().starts_with("Usage:"));
+    assert!(parse(&["help"]).unwrap_err().starts_with("Usage:"));
+    assert!(parse(&["ping"]).unwrap_err().starts_with("unknown command 'ping'"));
+    assert_eq!(
+        parse(&["cidr"]),
+        Err(String::from("cidr needs a block, e.g. cidr 10.0.0.0/8"))
+    );
+    assert_eq!(
+        parse(&["cidr", "10.0.0.0"]),
+        Err(String::from(
+            "'10.0.0.0' has no prefix length (expected ADDRESS/LENGTH, e.g. 10.0.0.0/8)"
+        ))
+    );
+    assert_eq!(
+        parse(&["cidr", "contains", "10.0.0.0/8", "10.0.0"]),
+        Err(String::from("'10.0.0' isn't a valid IPv4 address"))
+    );
+    assert_eq!(
+        parse(&["cidr", "split", "10.0.0.0/8", "x"]),
+        Err(String::from("'x' isn't a valid prefix length"))
+    );
+    assert_eq!(
+        parse(&["cidr", "split", "10.0.0.0/24", "16"]),
+        Err(String::from(
+            "can't split 10.0.0.0/24: /16 is too short: subnets of a /24 start at /24"
+        ))
+    );
+    assert_eq!(
+        parse(&["cidr", "split", "10.0.0.0/24", "40"]),
+        Err(String::from("can't split 10.0.0.0/24: /40 is too long: V4 prefixes go up to /32"))
+    );
+    assert_eq!(
+        parse(&["cidr", "aggregate"]),
+        Err(String::from("cidr aggregate needs at least one block"))
+    );
+    assert_eq!(
+        parse(&["route", "routes.txt"]),
+        Err(String::from("route needs at least one address to look up"))
+    );
+    assert_eq!(
+        parse(&["cidr", "10.0.0.0/8", "extra"]),
+        Err(String::from("unexpected argument 'extra' after cidr"))
+    );
+}
+
+#[test]
+fn output() {
+    assert_eq!(
+        run(&["cidr", "192.168.1.77/24"]),
+        "\
+Network:    192.168.1.0/24
+Netmask:    255.255.255.0
+Broadcast:  192.168.1.255
+First host: 192.168.1.1
+Last host:  192.168.1.254
+Hosts:      254
+"
+    );
+    assert!(!run(&["cidr", "2001:db8::/64"]).contains("Broadcast"));
+    assert_eq!(
+        run(&["cidr", "contains", "10.0.0.0/8", "10.20.0.0/16"]),
+        "Does 10.0.0.0/8 contain 10.20.0.0/16? true\n"
+    );
+    assert_eq!(
+        run(&["cidr", "split", "10.0.0.0/24", "25"]),
+        "10.0.0.0/25\n10.0.0.128/25\n"
+    );
+    assert!(run(&["cidr", "::/0"]).ends_with("Hosts:      2^128 (all of them)\n"));
+
+    let mut output = Vec::new();
+    assert!(cli::run(&Command::Split(cidr("10.0.0.0/24"), 16), &mut output).is_err());
+    assert!(output.is_empty());
+    assert_eq!(
+        run(&["cidr", "aggregate", "10.0.1.0/24", "10.0.0.0/24", "10.0.0.9/32"]),
+        "10.0.0.0/23\n"
+    );
+}
+
+#[test]
+fn route_lookups() {
+    assert_eq!(
+        run(&["route", "tests/fixtures/routes.txt", "10.20.30.40", "10.20.1.1", "8.8.8.8", "2001:db8::5"]),
+        "\
+10.20.30.40 -> via 10.20.0.1 (by 10.20.30.0/24)
+10.20.1.1 -> dev tun0 (by 10.20.0.0/16)
+8.8.8.8 -> via 192.168.1.254 (by 0.0.0.0/0)
+2001:db8::5 -> dev eth0 (by 2001:db8::/32)
+"
+    );
+
+    let mut output = Vec::new();
+    let missing = Command::Route(PathBuf::from("tests/fixtures/missing.txt"), vec![IpAddr::V4(1, 1, 1, 1)]);
+    assert!(cli::run(&missing, &mut output).is_err());
+}
+
+#[test]
+fn replays_scripts() {
+    assert_eq!(
+        run(&["replay", "tests/fixtures/script.txt"]),
+        "\
+line 6: 999 is out of range (colors go from 0 to 255)
+line 7: unknown message 'jump' (expected quit, move, write or color)
+cursor:  (3, 4)
+color:   255 0 0
+text:    \"hello, world\"
+running: false
+"
+    );
+}
@@ -0,0 +1,151 @@
+use std::net;
+
+use enums_and_pattern_matching::ip::{IpAddr, IpAddrKind, IpParseError};
+
+// Every one of these should parse (or not) exactly as `std::net::IpAddr`
+// does, and print back the same way.
+const CONFORMANCE: [&str; 61] = [
+    // IPv4
+    "0.0.0.0",
+    "127.0.0.1",
+    "255.255.255.255",
+    "192.168.1.10",
+    "1.2.3",
+    "1.2.3.4.5",
+    "256.0.0.1",
+    "01.2.3.4",
+    "1.2.3.04",
+    "1..3.4",
+    "1.2.3.4.",
+    ".1.2.3.4",
+    "1.2.3.-4",
+    "+1.2.3.4",
+    "1.2.3.4 ",
+    "1.2.3.a",
+    "1000.2.3.4",
+    // IPv6
+    "::",
+    "::1",
+    "1::",
+    "2001:db8::1",
+    "2001:DB8:0:0:0:0:0:1",
+    "2001:0db8:0000:0000:0000:0000:0000:0001",
+    "2001:db8:0:1:1:1:1:1",
+    "2001:db8:0:0:1:0:0:1",
+    "2001:0:0:1:0:0:0:1",
+    "1:0:0:0:1:0:0:0",
+    "0:0:1:0:0:1:0:0",
+    "1:2:3:4:5:6:7:8",
+    "1:2:3:4:5:6:7::",
+    "::2:3:4:5:6:7:8",
+    "fe80::1:2",
+    "ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff",
+    "::ffff:192.0.2.128",
+    "::ffff:c000:280",
+    "::192.0.2.128",
+    "64:ff9b::192.0.2.33",
+    "1:2:3:4:5:6:1.2.3.4",
+    "1:2:3:4:5:6:7:1.2.3.4",
+    "::ffff:1.2.3",
+    "::ffff:01.2.3.4",
+    "::1.2.3.4:5",
+    "1.2.3.4::",
+    "1.2.3.4::1",
+    "1:2:3:4:1.2.3.4::",
+    "1:2:3:4:5:6:7:8:9",
+    "1:2:3:4:5:6:7",
+    "1::2::3",
+    ":::",
+    ":1::2",
+    "1::2:",
+    "1:2:3:4:5:6:7:8::",
+    "::1:2:3:4:5:6:7:8",
+    "12345::",
+    "g::1",
+    ":",
+    "1:",
+    "[::1]",
+    "::1%eth0",
+    "",
+    "localhost",
+];
+
+#[test]
+fn parses_and_prints_like_std() {
+    for input in CONFORMANCE.iter() {
+        let ours = input.parse::<IpAddr>();
+        let theirs = input.parse::<net::IpAddr>();
+
+        match (&ours, &theirs) {
+            (Ok(ours), Ok(theirs)) => {
+                assert_eq!(IpAddr::from(*theirs), *ours, "parsing {}", input);
+                assert_eq!(ours.to_string(), theirs.to_string(), "printing {}", input);
+            }
+            (Err(_), Err(_)) => {}
+            _ => panic!("{}: we said {:?}, std said {:?}", input, ours, theirs),
+        }
+    }
+}
+
+#[test]
+fn prints_in_canonical_form() {
+    let canonical = |s: &str| s.parse::<IpAddr>().unwrap().to_string();
+
+    assert_eq!(canonical("2001:0DB8:0000:0000:0000:ff00:0042:8329"), "2001:db8::ff00:42:8329");
+    // A single zero segment isn't compressed.
+    assert_eq!(canonical("2001:db8:0:1:1:1:1:1"), "2001:db8:0:1:1:1:1:1");
+    // The longest run wins, and the first of equally long ones.
+    assert_eq!(canonical("2001:0:0:1:0:0:0:1"), "2001:0:0:1::1");
+    assert_eq!(canonical("2001:db8:0:0:1:0:0:1"), "2001:db8::1:0:0:1");
+    assert_eq!(canonical("::ffff:c000:0280"), "::ffff:192.0.2.128");
+}
+
+#[test]
+fn round_trips_through_std() {
+    let addresses = ["10.1.2.3", "::", "2001:db8::8a2e:370:7334", "::ffff:10.0.0.1"];
+
+    for address in addresses.iter() {
+        let ours: IpAddr = address.parse().unwrap();
+        let theirs = net::IpAddr::from(ours);
+        assert_eq!(theirs.to_string(), *address);
+        assert_eq!(IpAddr::from(theirs), ours);
+    }
+}
+
+#[test]
+fn maps_ipv4_into_ipv6_and_back() {
+    let v4 = IpAddr::V4(192, 0, 2, 128);
+    let v6 = v4.to_ipv6_mapped();
+
+    assert_eq!(v6, IpAddr::V6([0, 0, 0, 0, 0, 0xffff, 0xc000, 0x0280]));
+    assert_eq!(v6.to_ipv4_mapped(), Some(v4));
+    assert_eq!(v4.to_ipv4_mapped(), None);
+    assert_eq!("::1".parse::<IpAddr>().unwrap().to_ipv4_mapped(), None);
+}
+
+#[test]
+fn converts_to_and_from_bits() {
+    let v4: IpAddr = "10.0.0.1".parse().unwrap();
+    let v6: IpAddr = "2001:db8::1".parse().unwrap();
+
+    assert_eq!(v4.kind(), IpAddrKind::V4);
+    assert_eq!(v4.to_bits(), 0x0a00_0001);
+    assert_eq!(IpAddr::from_bits(IpAddrKind::V4, 0x0a00_0001), v4);
+    assert_eq!(v6.kind(), IpAddrKind::V6);
+    assert_eq!(v6.to_bits(), 0x2001_0db8_0000_0000_0000_0000_0000_0001);
+    assert_eq!(IpAddr::from_bits(IpAddrKind::V6, v6.to_bits()), v6);
+}
+
+#[test]
+fn reports_which_kind_of_address_was_invalid() {
+    assert_eq!("".parse::<IpAddr>(), Err(IpParseError::Empty));
+    assert_eq!(
+        "256.1.1.1".parse::<IpAddr>(),
+        Err(IpParseError::InvalidV4(String::from("256.1.1.1")))
+    );
+    assert_eq!("1::2::3".parse::<IpAddr>(), Err(IpParseError::InvalidV6(String::from("1::2::3"))));
+    assert_eq!(
+        IpParseError::InvalidV6(String::from("1::2::3")).to_string(),
+        "'1::2::3' isn't a valid IPv6 address"
+    );
+}
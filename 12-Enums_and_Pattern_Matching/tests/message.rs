@@ -0,0 +1,163 @@
+use std::io::Cursor;
+
+use enums_and_pattern_matching::message::{self, dispatch, DispatchError, Handler, Message, ParseError, State};
+
+fn parse(s: &str) -> Result<Message, ParseError> {
+    s.parse()
+}
+
+#[test]
+fn parses_messages() {
+    assert_eq!(parse("quit"), Ok(Message::Quit));
+    assert_eq!(parse("move 3 4"), Ok(Message::Move { x: 3, y: 4 }));
+    assert_eq!(parse("  move -3   4 "), Ok(Message::Move { x: -3, y: 4 }));
+    assert_eq!(parse("color 255 0 0"), Ok(Message::ChangeColor(255, 0, 0)));
+    assert_eq!(parse("write hello  world "), Ok(Message::Write(String::from("hello  world "))));
+    assert_eq!(parse("write"), Ok(Message::Write(String::new())));
+    assert_eq!(parse("write hi\r\n"), Ok(Message::Write(String::from("hi"))));
+}
+
+#[test]
+fn rejects_malformed_messages() {
+    assert_eq!(parse(""), Err(ParseError::Empty));
+    assert_eq!(parse("jump 1 2"), Err(ParseError::UnknownCommand(String::from("jump"))));
+    assert_eq!(parse("Quit"), Err(ParseError::UnknownCommand(String::from("Quit"))));
+    assert_eq!(parse("quit now"), Err(ParseError::Arguments("quit on its own")));
+    assert_eq!(parse("move 3"), Err(ParseError::Arguments("move X Y")));
+    assert_eq!(parse("color 1 2 3 4"), Err(ParseError::Arguments("color R G B")));
+    assert_eq!(parse("move 3 four"), Err(ParseError::Number(String::from("four"))));
+    assert_eq!(
+        parse("move 3 99999999999"),
+        Err(ParseError::Number(String::from("99999999999")))
+    );
+    assert_eq!(parse("move 3").unwrap_err().to_string(), "expected move X Y");
+}
+
+#[test]
+fn displays_as_it_parses() {
+    let messages = [
+        Message::Quit,
+        Message::Move { x: -1, y: 2 },
+        Message::Write(String::from("two  spaces")),
+        Message::Write(String::new()),
+        Message::ChangeColor(1, 2, 3),
+    ];
+
+    for message in messages.iter() {
+        assert_eq!(parse(&message.to_string()).as_ref(), Ok(message));
+    }
+}
+
+#[test]
+fn dispatches_to_the_state() {
+    let mut state = State::new();
+
+    dispatch(&mut state, &Message::Move { x: 3, y: 4 }).unwrap();
+    dispatch(&mut state, &Message::Write(String::from("hello"))).unwrap();
+    dispatch(&mut state, &Message::Write(String::from(" world"))).unwrap();
+    dispatch(&mut state, &Message::ChangeColor(255, 128, 0)).unwrap();
+
+    assert_eq!(
+        state,
+        State {
+            cursor: (3, 4),
+            text: String::from("hello world"),
+            color: (255, 128, 0),
+            running: true,
+        }
+    );
+}
+
+#[test]
+fn checks_messages_before_handling_them() {
+    let mut state = State::new();
+
+    assert_eq!(
+        dispatch(&mut state, &Message::ChangeColor(0, 256, -1)),
+        Err(DispatchError::ColorOutOfRange(256))
+    );
+    assert_eq!(state.color, (0, 0, 0));
+
+    dispatch(&mut state, &Message::Quit).unwrap();
+    assert!(!state.running);
+    assert_eq!(
+        dispatch(&mut state, &Message::Move { x: 1, y: 1 }),
+        Err(DispatchError::Stopped)
+    );
+    assert_eq!(dispatch(&mut state, &Message::Quit), Err(DispatchError::Stopped));
+    assert_eq!(state.cursor, (0, 0));
+}
+
+// A handler that just remembers what it was asked to do.
+#[derive(Default)]
+struct Log(Vec<String>);
+
+impl Handler for Log {
+    fn is_running(&self) -> bool {
+        true
+    }
+
+    fn quit(&mut self) {
+        self.0.push(String::from("quit"));
+    }
+
+    fn move_to(&mut self, x: i32, y: i32) {
+        self.0.push(format!("move_to({}, {})", x, y));
+    }
+
+    fn write(&mut self, text: &str) {
+        self.0.push(format!("write({:?})", text));
+    }
+
+    fn change_color(&mut self, r: u8, g: u8, b: u8) {
+        self.0.push(format!("change_color({}, {}, {})", r, g, b));
+    }
+}
+
+#[test]
+fn dispatches_to_any_handler() {
+    let mut log = Log::default();
+    let handler: &mut dyn Handler = &mut log;
+
+    for line in ["move 1 2", "write hi", "color 0 0 255", "quit"].iter() {
+        dispatch(handler, &line.parse().unwrap()).unwrap();
+    }
+
+    assert_eq!(
+        log.0,
+        ["move_to(1, 2)", "write(\"hi\")", "change_color(0, 0, 255)", "quit"]
+    );
+}
+
+#[test]
+fn replays_a_script() {
+    let script = "# a comment\nmove 1 1\n\nwrite a\nfly\nwrite b\ncolor 300 0 0\n";
+    let mut output = Vec::new();
+
+    let state = message::replay(Cursor::new(script), &mut output).unwrap();
+
+    assert_eq!(state.cursor, (1, 1));
+    assert_eq!(state.text, "ab");
+    assert!(state.running);
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "\
+line 5: unknown message 'fly' (expected quit, move, write or color)
+line 7: 300 is out of range (colors go from 0 to 255)
+"
+    );
+}
+
+#[test]
+fn replay_stops_at_quit() {
+    let mut output = Vec::new();
+    let state = message::replay(Cursor::new("write a\nquit\nwrite b\nnonsense\n"), &mut output).unwrap();
+
+    assert_eq!(state.text, "a");
+    assert!(!state.running);
+    assert!(output.is_empty());
+    assert_eq!(
+        state.to_string(),
+        "cursor:  (0, 0)\ncolor:   0 0 0\ntext:    \"a\"\nrunning: false"
+    );
+}
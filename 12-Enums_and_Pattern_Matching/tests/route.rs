@@ -0,0 +1,172 @@
+use std::io::Cursor;
+
+use enums_and_pattern_matching::cidr::{Cidr, CidrError};
+use enums_and_pattern_matching::ip::{IpAddr, IpParseError};
+use enums_and_pattern_matching::route::{NextHop, Route, RouteError, RouteParseError, RoutingTable};
+
+fn cidr(s: &str) -> Cidr {
+    s.parse().unwrap()
+}
+
+fn ip(s: &str) -> IpAddr {
+    s.parse().unwrap()
+}
+
+fn via(s: &str) -> NextHop {
+    NextHop::Gateway(ip(s))
+}
+
+fn dev(s: &str) -> NextHop {
+    NextHop::Interface(s.to_string())
+}
+
+// The matching prefix and next hop, as text.
+fn lookup(table: &RoutingTable, address: &str) -> Option<String> {
+    table
+        .lookup(&ip(address))
+        .map(|(prefix, hop)| format!("{} {}", prefix, hop))
+}
+
+#[test]
+fn finds_the_longest_prefix() {
+    let mut table = RoutingTable::new();
+    table.insert(cidr("10.0.0.0/8"), via("192.168.1.1"));
+    table.insert(cidr("10.1.0.0/16"), via("192.168.1.2"));
+    table.insert(cidr("10.1.2.0/24"), dev("eth1"));
+    table.insert(cidr("10.1.2.3/32"), dev("lo"));
+
+    assert_eq!(lookup(&table, "10.1.2.3").unwrap(), "10.1.2.3/32 dev lo");
+    assert_eq!(lookup(&table, "10.1.2.4").unwrap(), "10.1.2.0/24 dev eth1");
+    assert_eq!(lookup(&table, "10.1.3.4").unwrap(), "10.1.0.0/16 via 192.168.1.2");
+    assert_eq!(lookup(&table, "10.2.3.4").unwrap(), "10.0.0.0/8 via 192.168.1.1");
+    assert_eq!(lookup(&table, "11.0.0.0"), None);
+    assert_eq!(table.len(), 4);
+}
+
+#[test]
+fn keeps_v4_and_v6_apart() {
+    let mut table = RoutingTable::new();
+    table.insert(cidr("0.0.0.0/0"), via("192.168.1.254"));
+    assert_eq!(lookup(&table, "::1"), None);
+    // A V4-mapped address is a V6 address, so the V4 default doesn't cover it.
+    assert_eq!(lookup(&table, "::ffff:10.0.0.1"), None);
+
+    table.insert(cidr("::/0"), via("fe80::1"));
+    table.insert(cidr("2001:db8::/32"), dev("eth0"));
+    assert_eq!(lookup(&table, "2001:db8::1").unwrap(), "2001:db8::/32 dev eth0");
+    assert_eq!(lookup(&table, "2001:db9::1").unwrap(), "::/0 via fe80::1");
+    assert_eq!(lookup(&table, "8.8.8.8").unwrap(), "0.0.0.0/0 via 192.168.1.254");
+}
+
+#[test]
+fn inserts_replace_and_removes_fall_back() {
+    let mut table = RoutingTable::new();
+    assert!(table.is_empty());
+
+    assert_eq!(table.insert(cidr("10.0.0.0/8"), via("192.168.1.1")), None);
+    assert_eq!(table.insert(cidr("10.1.0.0/16"), dev("eth1")), None);
+    assert_eq!(
+        table.insert(cidr("10.1.0.0/16"), dev("eth2")),
+        Some(dev("eth1"))
+    );
+    assert_eq!(table.len(), 2);
+    assert_eq!(table.get(&cidr("10.1.0.0/16")), Some(&dev("eth2")));
+    assert_eq!(table.get(&cidr("10.1.0.0/17")), None);
+
+    assert_eq!(table.remove(&cidr("10.1.0.0/16")), Some(dev("eth2")));
+    assert_eq!(table.remove(&cidr("10.1.0.0/16")), None);
+    assert_eq!(table.remove(&cidr("172.16.0.0/12")), None);
+    assert_eq!(table.len(), 1);
+    assert_eq!(lookup(&table, "10.1.2.3").unwrap(), "10.0.0.0/8 via 192.168.1.1");
+
+    // Removing a route leaves routes further down alone.
+    table.insert(cidr("10.1.2.0/24"), dev("eth3"));
+    table.remove(&cidr("10.0.0.0/8"));
+    assert_eq!(lookup(&table, "10.1.2.3").unwrap(), "10.1.2.0/24 dev eth3");
+    assert_eq!(lookup(&table, "10.9.9.9"), None);
+}
+
+#[test]
+fn lists_routes_in_order() {
+    let mut table = RoutingTable::new();
+    let lines = [
+        "2001:db8::/32 dev eth0",
+        "10.1.0.0/16 dev eth1",
+        "10.0.0.0/8 via 10.0.0.1",
+        "0.0.0.0/0 via 10.0.0.254",
+    ];
+    for route in lines.iter() {
+        let route: Route = route.parse().unwrap();
+        table.insert(route.prefix, route.next_hop);
+    }
+    table.remove(&cidr("10.0.0.0/8"));
+
+    let routes: Vec<String> = table.routes().iter().map(|r| r.to_string()).collect();
+    assert_eq!(
+        routes,
+        ["0.0.0.0/0 via 10.0.0.254", "10.1.0.0/16 dev eth1", "2001:db8::/32 dev eth0"]
+    );
+}
+
+#[test]
+fn parses_routes() {
+    assert_eq!(
+        "10.0.0.0/8 via 192.168.1.1".parse::<Route>(),
+        Ok(Route {
+            prefix: cidr("10.0.0.0/8"),
+            next_hop: via("192.168.1.1")
+        })
+    );
+    assert_eq!("default via 10.0.0.1".parse::<Route>().unwrap().prefix, cidr("0.0.0.0/0"));
+    assert_eq!("default via fe80::1".parse::<Route>().unwrap().prefix, cidr("::/0"));
+    assert_eq!("  fd00::/8   dev  wg0 ".parse::<Route>().unwrap().next_hop, dev("wg0"));
+
+    assert_eq!(
+        "10.0.0.0/8".parse::<Route>(),
+        Err(RouteParseError::Syntax(String::from("10.0.0.0/8")))
+    );
+    assert_eq!(
+        "10.0.0.0/8 via".parse::<Route>(),
+        Err(RouteParseError::Syntax(String::from("10.0.0.0/8 via")))
+    );
+    assert_eq!(
+        "10.0.0.0/40 dev eth0".parse::<Route>(),
+        Err(RouteParseError::Prefix(CidrError::PrefixTooLong {
+            prefix: 40,
+            kind: enums_and_pattern_matching::ip::IpAddrKind::V4
+        }))
+    );
+    assert_eq!(
+        "10.0.0.0/8 via 10.0.0".parse::<Route>(),
+        Err(RouteParseError::Gateway(IpParseError::InvalidV4(String::from("10.0.0"))))
+    );
+}
+
+#[test]
+fn loads_a_route_file() {
+    let table = RoutingTable::load("tests/fixtures/routes.txt").unwrap();
+
+    assert_eq!(table.len(), 8);
+    assert_eq!(lookup(&table, "192.168.1.20").unwrap(), "192.168.1.0/24 dev eth0");
+    assert_eq!(lookup(&table, "10.20.30.1").unwrap(), "10.20.30.0/24 via 10.20.0.1");
+    assert_eq!(lookup(&table, "2001:db8:1::9").unwrap(), "2001:db8:1::/48 via 2001:db8::1");
+    assert_eq!(lookup(&table, "2001:4860::8888").unwrap(), "::/0 via fe80::1");
+}
+
+#[test]
+fn reports_the_bad_line() {
+    let text = "# routes\n10.0.0.0/8 via 10.0.0.1\n\n10.1.0.0/16 by 10.0.0.2\n";
+
+    match RoutingTable::read(Cursor::new(text)) {
+        Err(RouteError::Line { line, error }) => {
+            assert_eq!(line, 4);
+            assert_eq!(error, RouteParseError::Syntax(String::from("10.1.0.0/16 by 10.0.0.2")));
+        }
+        other => panic!("expected a bad line, got {:?}", other),
+    }
+
+    match RoutingTable::load("tests/fixtures/missing.txt") {
+        Err(RouteError::Io(_)) => {}
+        other => panic!("expected an I/O error, got {:?}", other),
+    }
+}
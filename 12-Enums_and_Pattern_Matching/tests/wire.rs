@@ -0,0 +1,195 @@
+use std::io::{self, BufReader, Cursor, Read};
+
+use proptest::prelude::*;
+
+use enums_and_pattern_matching::message::Message;
+use enums_and_pattern_matching::wire::{self, DecodeError, MessageReader, VERSION};
+
+fn any_message() -> impl Strategy<Value = Message> {
+    prop_oneof![
+        Just(Message::Quit),
+        (any::<i32>(), any::<i32>()).prop_map(|(x, y)| Message::Move { x, y }),
+        any::<String>().prop_map(Message::Write),
+        (any::<i32>(), any::<i32>(), any::<i32>()).prop_map(|(r, g, b)| Message::ChangeColor(r, g, b)),
+    ]
+}
+
+proptest! {
+    // Quit has nothing in it to vary, so it's covered here.
+    #[test]
+    fn every_variant_round_trips(message in any_message()) {
+        let bytes = wire::encode(&message);
+        prop_assert_eq!(wire::decode(&bytes).unwrap(), (message, bytes.len()));
+    }
+
+    #[test]
+    fn move_round_trips(x in any::<i32>(), y in any::<i32>()) {
+        let message = Message::Move { x, y };
+        let bytes = wire::encode(&message);
+        prop_assert_eq!(wire::decode(&bytes).unwrap(), (message, 10));
+    }
+
+    #[test]
+    fn write_round_trips(text in any::<String>()) {
+        let bytes = wire::encode(&Message::Write(text.clone()));
+        prop_assert_eq!(bytes.len(), 6 + text.len());
+        prop_assert_eq!(wire::decode(&bytes).unwrap(), (Message::Write(text), bytes.len()));
+    }
+
+    #[test]
+    fn change_color_round_trips(r in any::<i32>(), g in any::<i32>(), b in any::<i32>()) {
+        let message = Message::ChangeColor(r, g, b);
+        let bytes = wire::encode(&message);
+        prop_assert_eq!(wire::decode(&bytes).unwrap(), (message, 14));
+    }
+
+    #[test]
+    fn cut_short_messages_are_truncated(message in any_message(), cut in any::<prop::sample::Index>()) {
+        let bytes = wire::encode(&message);
+        let cut = cut.index(bytes.len());
+        prop_assert!(matches!(wire::decode(&bytes[..cut]), Err(DecodeError::Truncated)));
+    }
+
+    #[test]
+    fn streams_round_trip(messages in prop::collection::vec(any_message(), 0..20)) {
+        let mut stream = Vec::new();
+        for message in &messages {
+            wire::write_message(&mut stream, message).unwrap();
+        }
+
+        let read: Vec<Message> = MessageReader::new(Cursor::new(stream))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        prop_assert_eq!(read, messages);
+    }
+}
+
+#[test]
+fn encodes_each_variant() {
+    assert_eq!(wire::encode(&Message::Quit), [VERSION, 0]);
+    assert_eq!(
+        wire::encode(&Message::Move { x: 1, y: -1 }),
+        [VERSION, 1, 0, 0, 0, 1, 0xff, 0xff, 0xff, 0xff]
+    );
+    assert_eq!(
+        wire::encode(&Message::Write(String::from("hé"))),
+        [VERSION, 2, 0, 0, 0, 3, b'h', 0xc3, 0xa9]
+    );
+    assert_eq!(
+        wire::encode(&Message::ChangeColor(255, 0, 256)),
+        [VERSION, 3, 0, 0, 0, 255, 0, 0, 0, 0, 0, 0, 1, 0]
+    );
+}
+
+#[test]
+fn decodes_only_the_first_message() {
+    let mut bytes = wire::encode(&Message::Move { x: 3, y: 4 });
+    bytes.extend(wire::encode(&Message::Quit));
+
+    assert_eq!(wire::decode(&bytes).unwrap(), (Message::Move { x: 3, y: 4 }, 10));
+    assert_eq!(wire::decode(&bytes[10..]).unwrap(), (Message::Quit, 2));
+}
+
+#[test]
+fn rejects_bad_input() {
+    assert!(matches!(wire::decode(&[]), Err(DecodeError::Truncated)));
+    assert!(matches!(wire::decode(&[VERSION]), Err(DecodeError::Truncated)));
+    assert!(matches!(wire::decode(&[VERSION, 4]), Err(DecodeError::UnknownTag(4))));
+    assert!(matches!(wire::decode(&[VERSION, 0xff]), Err(DecodeError::UnknownTag(0xff))));
+    assert!(matches!(wire::decode(&[2, 0]), Err(DecodeError::UnknownVersion(2))));
+    assert!(matches!(wire::decode(&[0, 0]), Err(DecodeError::UnknownVersion(0))));
+    assert!(matches!(
+        wire::decode(&[VERSION, 2, 0, 0, 0, 2, 0xc3, 0x28]),
+        Err(DecodeError::InvalidUtf8(_))
+    ));
+    // A length promising far more than there is doesn't try to allocate it.
+    assert!(matches!(
+        wire::decode(&[VERSION, 2, 0xff, 0xff, 0xff, 0xff, b'a']),
+        Err(DecodeError::Truncated)
+    ));
+    assert_eq!(
+        DecodeError::UnknownTag(9).to_string(),
+        "there's no message with tag 9"
+    );
+}
+
+#[test]
+fn reader_stops_at_the_first_error() {
+    let mut stream = wire::encode(&Message::Write(String::from("ok")));
+    stream.extend_from_slice(&[VERSION, 7]);
+    stream.extend(wire::encode(&Message::Quit));
+
+    let mut reader = MessageReader::new(stream.as_slice());
+    assert_eq!(reader.next().unwrap().unwrap(), Message::Write(String::from("ok")));
+    assert!(matches!(reader.next(), Some(Err(DecodeError::UnknownTag(7)))));
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn reader_reports_a_stream_cut_mid_message() {
+    let mut stream = wire::encode(&Message::Quit);
+    stream.extend_from_slice(&wire::encode(&Message::Move { x: 1, y: 2 })[..5]);
+
+    let results: Vec<_> = MessageReader::new(BufReader::new(stream.as_slice())).collect();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].as_ref().unwrap(), &Message::Quit);
+    assert!(matches!(results[1], Err(DecodeError::Truncated)));
+}
+
+// Hands out one byte per read, and is interrupted before each, like a slow
+// socket.
+struct Trickle<'a> {
+    bytes: &'a [u8],
+    interrupt: bool,
+}
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.interrupt = !self.interrupt;
+        if self.interrupt {
+            return Err(io::Error::new(io::ErrorKind::Interrupted, "interrupted"));
+        }
+        match self.bytes.split_first() {
+            Some((first, rest)) if !buf.is_empty() => {
+                buf[0] = *first;
+                self.bytes = rest;
+                Ok(1)
+            }
+            _ => Ok(0),
+        }
+    }
+}
+
+#[test]
+fn reader_copes_with_short_and_interrupted_reads() {
+    let messages = [
+        Message::ChangeColor(1, 2, 3),
+        Message::Write(String::from("slowly")),
+        Message::Quit,
+    ];
+    let stream: Vec<u8> = messages.iter().flat_map(wire::encode).collect();
+
+    let reader = MessageReader::new(Trickle {
+        bytes: &stream,
+        interrupt: false,
+    });
+    let read: Vec<Message> = reader.collect::<Result<_, _>>().unwrap();
+    assert_eq!(read, messages);
+}
+
+#[test]
+fn reader_passes_on_io_errors() {
+    struct Broken;
+
+    impl Read for Broken {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::ConnectionReset, "reset"))
+        }
+    }
+
+    let mut reader = MessageReader::new(Broken);
+    match reader.next() {
+        Some(Err(DecodeError::Io(e))) => assert_eq!(e.kind(), io::ErrorKind::ConnectionReset),
+        other => panic!("expected an I/O error, got {:?}", other),
+    }
+}